@@ -0,0 +1,245 @@
+//! x25519 key agreement with the MXE and the Rescue cipher, in counter mode
+//! over the Curve25519 base field, that `Enc<Shared, _>` values travel in.
+//!
+//! Mirrors Arcium's `RescueCipher`: the cipher key is HKDF over Rescue-Prime
+//! of the x25519 shared secret, and both the cipher and the hash are Rescue
+//! with the S-box `x^5` and the Cauchy MDS matrix `1 / (i + j + 2)`.
+
+use ark_ff::{AdditiveGroup, BigInteger, Field, Fp256, MontBackend, MontConfig, PrimeField};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+/// 2^255 - 19
+#[derive(MontConfig)]
+#[modulus = "57896044618658097711785492504343953926634992332820282019728792003956564819949"]
+#[generator = "2"]
+pub struct FqConfig;
+pub type Fq = Fp256<MontBackend<FqConfig, 4>>;
+
+// The smallest prime not dividing p - 1, so x -> x^5 is a permutation of Fq
+const ALPHA: u64 = 5;
+// 5^-1 mod (p - 1), little-endian limbs
+const ALPHA_INV: [u64; 4] = [
+    14757395258967641281,
+    14757395258967641292,
+    14757395258967641292,
+    5534023222112865484,
+];
+
+// Cipher state: one counter block yields this many keystream elements
+pub const BLOCK_LEN: usize = 5;
+// 2 * max(l0, l1, 5) from the Rescue paper at 128-bit security
+const CIPHER_ROUNDS: usize = 10;
+const CIPHER_SEED: &[u8] = b"encrypt everything, compute anything";
+
+// Rescue-Prime with capacity 1; the rounds are the Groebner basis bound plus 50%
+const HASH_WIDTH: usize = 6;
+const HASH_RATE: usize = 5;
+const HASH_ROUNDS: usize = 8;
+
+/// `secret`'s x25519 public key, what goes into `Argument::ArcisPubkey`.
+pub fn x25519_public_key(secret: &[u8; 32]) -> [u8; 32] {
+    MontgomeryPoint::mul_base_clamped(*secret).to_bytes()
+}
+
+/// The x25519 shared secret between `secret` and the MXE's public key.
+pub fn x25519_shared_secret(secret: &[u8; 32], mxe_public_key: &[u8; 32]) -> [u8; 32] {
+    MontgomeryPoint(*mxe_public_key)
+        .mul_clamped(*secret)
+        .to_bytes()
+}
+
+// Field elements from SHAKE256, 16 bytes wider than p so the reduction bias
+// is negligible
+struct Sampler(<Shake256 as ExtendableOutput>::Reader);
+
+impl Sampler {
+    fn new(seed: &[u8]) -> Self {
+        let mut shake = Shake256::default();
+        shake.update(seed);
+        Self(shake.finalize_xof())
+    }
+
+    fn next(&mut self) -> Fq {
+        let mut bytes = [0u8; 48];
+        self.0.read(&mut bytes);
+        Fq::from_le_bytes_mod_order(&bytes)
+    }
+
+    fn vector<const M: usize>(&mut self) -> [Fq; M] {
+        core::array::from_fn(|_| self.next())
+    }
+}
+
+fn mix<const M: usize>(matrix: &[[Fq; M]; M], state: &[Fq; M]) -> [Fq; M] {
+    core::array::from_fn(|i| matrix[i].iter().zip(state).map(|(m, s)| *m * s).sum())
+}
+
+fn add<const M: usize>(a: &[Fq; M], b: &[Fq; M]) -> [Fq; M] {
+    core::array::from_fn(|i| a[i] + b[i])
+}
+
+/// Rescue permutation of width `M`. Half rounds alternate the S-box and its
+/// inverse, each followed by the MDS matrix and the next round key.
+struct Rescue<const M: usize> {
+    mds: [[Fq; M]; M],
+    round_keys: Vec<[Fq; M]>,
+    // The block cipher starts with x^(1/5), the hash with x^5
+    inverse_first: bool,
+}
+
+impl<const M: usize> Rescue<M> {
+    fn new(round_keys: Vec<[Fq; M]>, inverse_first: bool) -> Self {
+        // Cauchy matrix over x_i = i + 1, y_j = -(j + 1)
+        let mds = core::array::from_fn(|i| {
+            core::array::from_fn(|j| Fq::from((i + j + 2) as u64).inverse().unwrap())
+        });
+        Self {
+            mds,
+            round_keys,
+            inverse_first,
+        }
+    }
+
+    /// Every intermediate state, starting with `state` plus the first key.
+    fn states(&self, state: &[Fq; M]) -> Vec<[Fq; M]> {
+        let mut state = add(state, &self.round_keys[0]);
+        let mut states = vec![state];
+        for (r, key) in self.round_keys[1..].iter().enumerate() {
+            let inverse = (r % 2 == 0) == self.inverse_first;
+            let boxed = state.map(|x| {
+                if inverse {
+                    x.pow(ALPHA_INV)
+                } else {
+                    x.pow([ALPHA])
+                }
+            });
+            state = add(&mix(&self.mds, &boxed), key);
+            states.push(state);
+        }
+        states
+    }
+
+    fn permute(&self, state: &[Fq; M]) -> [Fq; M] {
+        *self.states(state).last().unwrap()
+    }
+}
+
+/// Rescue-Prime sponge, the hash under the key derivation.
+fn hash(message: &[Fq]) -> [Fq; HASH_RATE] {
+    let seed = format!(
+        "Rescue-XLIX({},{HASH_WIDTH},{},128)",
+        Fq::MODULUS,
+        HASH_WIDTH - HASH_RATE
+    );
+    let mut sampler = Sampler::new(seed.as_bytes());
+    let round_keys = core::iter::once([Fq::ZERO; HASH_WIDTH])
+        .chain((0..2 * HASH_ROUNDS).map(|_| sampler.vector()))
+        .collect();
+    let rescue = Rescue::<HASH_WIDTH>::new(round_keys, false);
+
+    // pad with a one, then zeros up to a whole number of blocks
+    let mut padded = message.to_vec();
+    padded.push(Fq::ONE);
+    padded.resize(padded.len().next_multiple_of(HASH_RATE), Fq::ZERO);
+
+    let mut state = [Fq::ZERO; HASH_WIDTH];
+    for block in padded.chunks(HASH_RATE) {
+        for (s, x) in state.iter_mut().zip(block) {
+            *s += x;
+        }
+        state = rescue.permute(&state);
+    }
+    core::array::from_fn(|i| state[i])
+}
+
+/// RFC 2104 with a block of `HASH_RATE` field elements; `key` is at most one
+/// block.
+fn hmac(key: &[Fq], message: &[Fq]) -> [Fq; HASH_RATE] {
+    let ipad = from_bytes(&[0x36; 32]);
+    let opad = from_bytes(&[0x5c; 32]);
+    let mut key = key.to_vec();
+    key.resize(HASH_RATE, Fq::ZERO);
+
+    let inner: Vec<Fq> = key
+        .iter()
+        .map(|k| *k + ipad)
+        .chain(message.iter().copied())
+        .collect();
+    let outer: Vec<Fq> = key.iter().map(|k| *k + opad).chain(hash(&inner)).collect();
+    hash(&outer)
+}
+
+/// RFC 5869 with an empty salt and info, one block of output.
+fn hkdf(ikm: &[Fq]) -> [Fq; HASH_RATE] {
+    let prk = hmac(&[Fq::ZERO; HASH_RATE], ikm);
+    hmac(&prk, &[Fq::ONE])
+}
+
+/// Little-endian, canonical: the layout of a ciphertext on chain.
+pub fn to_bytes(x: &Fq) -> [u8; 32] {
+    x.into_bigint().to_bytes_le().try_into().unwrap()
+}
+
+pub fn from_bytes(bytes: &[u8; 32]) -> Fq {
+    Fq::from_le_bytes_mod_order(bytes)
+}
+
+/// Counter-mode Rescue keyed by an x25519 shared secret. Ciphertext `i` is
+/// plaintext `i` plus element `i % 5` of the encrypted block
+/// `[nonce, i / 5, 0, 0, 0]`.
+pub struct RescueCipher {
+    rescue: Rescue<BLOCK_LEN>,
+}
+
+impl RescueCipher {
+    pub fn new(shared_secret: &[u8; 32]) -> Self {
+        let key = hkdf(&[from_bytes(shared_secret)]);
+
+        // Round constants follow k[i + 1] = A * k[i] + b from a seeded
+        // sample of A, k[0] and b; the key schedule runs the key through
+        // the permutation under them and keeps every state as a round key
+        let mut sampler = Sampler::new(CIPHER_SEED);
+        let matrix: [[Fq; BLOCK_LEN]; BLOCK_LEN] = core::array::from_fn(|_| sampler.vector());
+        let initial = sampler.vector();
+        let affine = sampler.vector();
+        let constants =
+            core::iter::successors(Some(initial), |k| Some(add(&mix(&matrix, k), &affine)))
+                .take(2 * CIPHER_ROUNDS + 1)
+                .collect();
+        let round_keys = Rescue::new(constants, true).states(&key);
+
+        Self {
+            rescue: Rescue::new(round_keys, true),
+        }
+    }
+
+    fn keystream(&self, nonce: u128, len: usize) -> Vec<Fq> {
+        (0..len.div_ceil(BLOCK_LEN))
+            .flat_map(|block| {
+                let mut counter = [Fq::ZERO; BLOCK_LEN];
+                counter[0] = Fq::from(nonce);
+                counter[1] = Fq::from(block as u64);
+                self.rescue.permute(&counter)
+            })
+            .take(len)
+            .collect()
+    }
+
+    pub fn encrypt(&self, plaintext: &[Fq], nonce: u128) -> Vec<[u8; 32]> {
+        plaintext
+            .iter()
+            .zip(self.keystream(nonce, plaintext.len()))
+            .map(|(p, k)| to_bytes(&(*p + k)))
+            .collect()
+    }
+
+    pub fn decrypt(&self, ciphertexts: &[[u8; 32]], nonce: u128) -> Vec<Fq> {
+        ciphertexts
+            .iter()
+            .zip(self.keystream(nonce, ciphertexts.len()))
+            .map(|(c, k)| from_bytes(c) - k)
+            .collect()
+    }
+}
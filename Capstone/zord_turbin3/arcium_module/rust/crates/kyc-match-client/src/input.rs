@@ -0,0 +1,151 @@
+use rust::KYC_MATCH_INPUT_LEN;
+use sha3::{Digest, Sha3_256};
+
+use crate::cipher::{self, Fq, RescueCipher};
+
+// Must match `KycMatchInput` in encrypted-ixs
+pub const NAME_LEN: usize = 16;
+pub const PASSPORT_NUMBER_LEN: usize = 9;
+pub const PAN_LEN: usize = 10;
+
+// Reason bits of `KycMatchResult`, 0 when the match passed
+pub const REASON_DOB_MISMATCH: u8 = 1;
+pub const REASON_NAME_MISMATCH: u8 = 2;
+pub const REASON_PASSPORT_CHECKSUM: u8 = 4;
+pub const REASON_PAN_FORMAT: u8 = 8;
+pub const REASON_NOT_ATTESTED: u8 = 16;
+
+/// Plaintext mirror of the circuit's `KycMatchInput`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KycMatchInput {
+    pub passport_name: [u8; NAME_LEN],
+    pub pan_name: [u8; NAME_LEN],
+    /// YYYYMMDD
+    pub passport_dob: u32,
+    pub pan_dob: u32,
+    pub passport_number: [u8; PASSPORT_NUMBER_LEN],
+    /// ASCII digit from the MRZ
+    pub passport_check_digit: u8,
+    pub pan_number: [u8; PAN_LEN],
+    /// From the attester, along with the documents it committed to
+    pub salt: u128,
+}
+
+impl KycMatchInput {
+    /// One field element per ciphertext, in the circuit's field order.
+    pub fn to_field_elements(&self) -> [Fq; KYC_MATCH_INPUT_LEN] {
+        let elements: Vec<Fq> = self
+            .passport_name
+            .iter()
+            .chain(&self.pan_name)
+            .map(|&b| Fq::from(b))
+            .chain([Fq::from(self.passport_dob), Fq::from(self.pan_dob)])
+            .chain(self.passport_number.iter().map(|&b| Fq::from(b)))
+            .chain([Fq::from(self.passport_check_digit)])
+            .chain(self.pan_number.iter().map(|&b| Fq::from(b)))
+            .chain([Fq::from(self.salt)])
+            .collect();
+        elements.try_into().unwrap()
+    }
+
+    /// The `document_commitment` an attester signs into zord's `Attestation`
+    /// for these documents: sha3-256 over the fields in order, integers
+    /// little-endian. The circuit fails any input not hashing to the one on
+    /// the user's `KycAccount`.
+    pub fn document_commitment(&self) -> [u8; 32] {
+        Sha3_256::new()
+            .chain_update(self.passport_name)
+            .chain_update(self.pan_name)
+            .chain_update(self.passport_dob.to_le_bytes())
+            .chain_update(self.pan_dob.to_le_bytes())
+            .chain_update(self.passport_number)
+            .chain_update([self.passport_check_digit])
+            .chain_update(self.pan_number)
+            .chain_update(self.salt.to_le_bytes())
+            .finalize()
+            .into()
+    }
+}
+
+/// Uppercases `name`, keeps A-Z and single spaces, and zero-pads or truncates
+/// it to `NAME_LEN`, the form the circuit's edit distance expects.
+pub fn normalize_name(name: &str) -> [u8; NAME_LEN] {
+    let mut out = [0u8; NAME_LEN];
+    let mut len = 0;
+    for word in name.split_whitespace() {
+        let letters = word
+            .bytes()
+            .map(|b| b.to_ascii_uppercase())
+            .filter(u8::is_ascii_uppercase);
+        if len > 0 && len < NAME_LEN {
+            out[len] = b' ';
+            len += 1;
+        }
+        for b in letters.take(NAME_LEN - len) {
+            out[len] = b;
+            len += 1;
+        }
+    }
+    out
+}
+
+/// What `write_kyc_match_input` and `kyc_match` need from the user side.
+pub struct EncryptedInput {
+    pub ciphertexts: [[u8; 32]; KYC_MATCH_INPUT_LEN],
+    /// The user's x25519 public key, `pub_key` of `kyc_match`
+    pub pub_key: [u8; 32],
+    pub nonce: u128,
+}
+
+/// Encrypts `input` under the secret shared between `secret` and the MXE.
+/// A nonce must never be reused with the same key.
+pub fn encrypt_input(
+    input: &KycMatchInput,
+    secret: &[u8; 32],
+    mxe_public_key: &[u8; 32],
+    nonce: u128,
+) -> EncryptedInput {
+    let cipher = RescueCipher::new(&cipher::x25519_shared_secret(secret, mxe_public_key));
+    EncryptedInput {
+        ciphertexts: cipher
+            .encrypt(&input.to_field_elements(), nonce)
+            .try_into()
+            .unwrap(),
+        pub_key: cipher::x25519_public_key(secret),
+        nonce,
+    }
+}
+
+/// The circuit's `KycMatchResult`, as decrypted by one of its recipients.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KycMatchResult {
+    pub passed: bool,
+    pub reason: u8,
+}
+
+/// Decrypts one `result` / `nonce` pair of `KycMatchEvent` (the user's, the
+/// attester's or the auditor's, with the matching `secret`). `None` if the
+/// plaintext is not a well-formed result, i.e. the key or nonce is wrong.
+pub fn decrypt_result(
+    secret: &[u8; 32],
+    mxe_public_key: &[u8; 32],
+    result: &[[u8; 32]; 2],
+    nonce: [u8; 16],
+) -> Option<KycMatchResult> {
+    let cipher = RescueCipher::new(&cipher::x25519_shared_secret(secret, mxe_public_key));
+    let plaintext = cipher.decrypt(result, u128::from_le_bytes(nonce));
+
+    let passed = match plaintext[0] {
+        x if x == Fq::from(0u8) => false,
+        x if x == Fq::from(1u8) => true,
+        _ => return None,
+    };
+    let reason = cipher::to_bytes(&plaintext[1]);
+    if reason[1..] != [0u8; 31] {
+        return None;
+    }
+    Some(KycMatchResult {
+        passed,
+        reason: reason[0],
+    })
+}
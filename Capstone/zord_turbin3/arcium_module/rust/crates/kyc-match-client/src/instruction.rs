@@ -0,0 +1,124 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use arcium_anchor::prelude::{ARCIUM_CLOCK_ACCOUNT_ADDRESS, ARCIUM_FEE_POOL_ACCOUNT_ADDRESS};
+use arcium_client::idl::arcium::ID as ARCIUM_PROGRAM_ID;
+use arcium_client::pda::{
+    cluster_acc, computation_acc, computation_definition_acc, execpool_acc, mempool_acc, mxe_acc,
+};
+
+use crate::input::EncryptedInput;
+use crate::pda;
+
+/// Ciphertexts per `write_kyc_match_input`, small enough for one transaction.
+pub const WRITE_CHUNK: usize = 16;
+
+/// `register_attester_key`, publishing the x25519 key an allow-listed zord
+/// attester receives its copy of each result under.
+pub fn register_attester_key(attester: &Pubkey, x25519_key: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: rust::ID,
+        accounts: rust::accounts::RegisterAttesterKey {
+            attester: *attester,
+            attester_config: pda::zord_attester_config().0,
+            attester_key: pda::attester_key(attester).0,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: rust::instruction::RegisterAttesterKey { x25519_key }.data(),
+    }
+}
+
+/// `write_kyc_match_input` calls staging `ciphertexts`, in order, for the
+/// `kyc_match` queued under `computation_offset`. All of them must land
+/// before it is queued.
+pub fn write_kyc_match_input(
+    payer: &Pubkey,
+    computation_offset: u64,
+    ciphertexts: &[[u8; 32]],
+) -> Vec<Instruction> {
+    ciphertexts
+        .chunks(WRITE_CHUNK)
+        .enumerate()
+        .map(|(i, chunk)| Instruction {
+            program_id: rust::ID,
+            accounts: rust::accounts::WriteKycMatchInput {
+                payer: *payer,
+                kyc_match_input: pda::kyc_match_input(payer, computation_offset).0,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: rust::instruction::WriteKycMatchInput {
+                computation_offset,
+                start: (i * WRITE_CHUNK) as u8,
+                ciphertexts: chunk.to_vec(),
+            }
+            .data(),
+        })
+        .collect()
+}
+
+/// `reclaim_kyc_match_input`, closing the input staged under
+/// `computation_offset` back to the payer when no callback did.
+pub fn reclaim_kyc_match_input(payer: &Pubkey, computation_offset: u64) -> Instruction {
+    Instruction {
+        program_id: rust::ID,
+        accounts: rust::accounts::ReclaimKycMatchInput {
+            payer: *payer,
+            kyc_match_input: pda::kyc_match_input(payer, computation_offset).0,
+        }
+        .to_account_metas(None),
+        data: rust::instruction::ReclaimKycMatchInput { computation_offset }.data(),
+    }
+}
+
+/// Who the result is encrypted to besides the user.
+pub struct Recipients {
+    /// A zord attester with a registered `AttesterKey`
+    pub attester: Pubkey,
+    pub attester_nonce: u128,
+    /// The auditor key itself comes from `AuditorConfig`
+    pub auditor_nonce: u128,
+}
+
+/// `kyc_match` over the staged `input`. `computation_offset` must be unique
+/// per computation; `cluster_offset` is the cluster the MXE runs on.
+pub fn kyc_match(
+    payer: &Pubkey,
+    input: &EncryptedInput,
+    recipients: &Recipients,
+    computation_offset: u64,
+    cluster_offset: u32,
+) -> Instruction {
+    Instruction {
+        program_id: rust::ID,
+        accounts: rust::accounts::KycMatch {
+            payer: *payer,
+            sign_pda_account: pda::sign_pda().0,
+            mxe_account: mxe_acc(&rust::ID),
+            mempool_account: mempool_acc(&rust::ID),
+            executing_pool: execpool_acc(&rust::ID),
+            computation_account: computation_acc(&rust::ID, computation_offset),
+            comp_def_account: computation_definition_acc(&rust::ID, pda::KYC_MATCH_COMP_DEF_OFFSET),
+            cluster_account: cluster_acc(cluster_offset),
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            kyc_match_input: pda::kyc_match_input(payer, computation_offset).0,
+            auditor_config: pda::auditor_config().0,
+            attester_config: pda::zord_attester_config().0,
+            attester_key: pda::attester_key(&recipients.attester).0,
+            kyc_account: pda::zord_kyc(payer).0,
+            system_program: system_program::ID,
+            arcium_program: ARCIUM_PROGRAM_ID,
+        }
+        .to_account_metas(None),
+        data: rust::instruction::KycMatch {
+            computation_offset,
+            pub_key: input.pub_key,
+            nonce: input.nonce,
+            attester_nonce: recipients.attester_nonce,
+            auditor_nonce: recipients.auditor_nonce,
+        }
+        .data(),
+    }
+}
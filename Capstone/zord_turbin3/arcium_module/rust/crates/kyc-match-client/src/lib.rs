@@ -0,0 +1,15 @@
+//! Client side of the Arcium `kyc_match` computation: encrypting
+//! `KycMatchInput` to the MXE, the Arcium PDAs, the staging and queue
+//! instructions, and decrypting the results carried by `KycMatchEvent`.
+//!
+//! Instruction data and account lists come from the program crate itself
+//! (`rust::instruction`, `rust::accounts`), so they cannot drift from the IDL.
+
+pub mod cipher;
+pub mod input;
+pub mod instruction;
+pub mod pda;
+
+pub use cipher::{x25519_public_key, x25519_shared_secret, RescueCipher};
+pub use input::{decrypt_result, encrypt_input, EncryptedInput, KycMatchInput, KycMatchResult};
+pub use rust::ID;
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::Pubkey;
+use arcium_anchor::prelude::SIGN_PDA_SEED;
+
+// The accounts the Arcium program owns come from `arcium_client::pda`.
+
+/// Offset of the `kyc_match` computation definition.
+pub const KYC_MATCH_COMP_DEF_OFFSET: u32 = arcium_anchor::comp_def_offset("kyc_match");
+
+/// `[b"kyc_match_input", payer, computation_offset]`, where the payer stages
+/// the encrypted input of one computation.
+pub fn kyc_match_input(payer: &Pubkey, computation_offset: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"kyc_match_input",
+            payer.as_ref(),
+            &computation_offset.to_le_bytes(),
+        ],
+        &rust::ID,
+    )
+}
+
+/// `[b"attester_key", attester]`, the attester's registered x25519 key.
+pub fn attester_key(attester: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"attester_key", attester.as_ref()], &rust::ID)
+}
+
+/// zord's `[b"attesters"]` allow-list.
+pub fn zord_attester_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"attesters"], &zord::ID)
+}
+
+/// zord's `[b"kyc", user]` record the callback writes the result into.
+pub fn zord_kyc(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"kyc", user.as_ref()], &zord::ID)
+}
+
+/// `[b"auditor_config"]`, the auditor key every result is also encrypted to.
+pub fn auditor_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"auditor_config"], &rust::ID)
+}
+
+/// `[b"zord_authority"]`, which signs the callback's CPI into zord.
+pub fn zord_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"zord_authority"], &rust::ID)
+}
+
+/// The program's signer PDA for `queue_computation`.
+pub fn sign_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SIGN_PDA_SEED], &rust::ID)
+}
@@ -0,0 +1,292 @@
+//! Fixed keys and vectors for the encryption, and the builders' output
+//! round-tripped through the program's own instruction types.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::{system_program, AnchorDeserialize};
+use arcium_client::pda as arcium_pda;
+use arcium_client::ARCIUM_PROGRAM_ID;
+use kyc_match_client::cipher::Fq;
+use kyc_match_client::input::{normalize_name, NAME_LEN, REASON_DOB_MISMATCH};
+use kyc_match_client::instruction::{self, Recipients, WRITE_CHUNK};
+use kyc_match_client::{
+    decrypt_result, encrypt_input, pda, x25519_public_key, x25519_shared_secret, KycMatchInput,
+    KycMatchResult, RescueCipher,
+};
+use rust::KYC_MATCH_INPUT_LEN;
+use sha2::{Digest, Sha256};
+
+// RFC 7748 section 6.1: Alice plays the user, Bob the MXE
+const ALICE_SECRET: &str = "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a";
+const ALICE_PUBLIC: &str = "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+const BOB_SECRET: &str = "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb";
+const BOB_PUBLIC: &str = "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f";
+const SHARED_SECRET: &str = "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742";
+
+const NONCE: u128 = 42;
+
+fn key(hex_str: &str) -> [u8; 32] {
+    hex::decode(hex_str).unwrap().try_into().unwrap()
+}
+
+fn input() -> KycMatchInput {
+    KycMatchInput {
+        passport_name: normalize_name("Ananya Sharma"),
+        pan_name: normalize_name("ANANYA  SHARMA"),
+        passport_dob: 19990412,
+        pan_dob: 19990412,
+        passport_number: *b"L898902C3",
+        passport_check_digit: b'6',
+        pan_number: *b"ABCPE1234F",
+        salt: 0x0f0e0d0c0b0a09080706050403020100,
+    }
+}
+
+fn sighash(name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("global:{name}"));
+    hash[..8].try_into().unwrap()
+}
+
+fn args<T: AnchorDeserialize>(ix: &Instruction, name: &str) -> T {
+    assert_eq!(ix.program_id, rust::ID);
+    assert_eq!(ix.data[..8], sighash(name), "{name} discriminator");
+    T::try_from_slice(&ix.data[8..]).unwrap()
+}
+
+#[test]
+fn x25519_matches_rfc_7748() {
+    let alice = key(ALICE_SECRET);
+    let bob = key(BOB_SECRET);
+
+    assert_eq!(x25519_public_key(&alice), key(ALICE_PUBLIC));
+    assert_eq!(x25519_public_key(&bob), key(BOB_PUBLIC));
+    assert_eq!(x25519_shared_secret(&alice, &key(BOB_PUBLIC)), key(SHARED_SECRET));
+    assert_eq!(x25519_shared_secret(&bob, &key(ALICE_PUBLIC)), key(SHARED_SECRET));
+}
+
+#[test]
+fn names_are_normalized_for_the_circuit() {
+    let mut expected = [0u8; NAME_LEN];
+    expected[..13].copy_from_slice(b"ANANYA SHARMA");
+    assert_eq!(normalize_name("  ananya   sharma "), expected);
+    assert_eq!(normalize_name("Ananya Sharma-Iyer"), {
+        let mut e = [0u8; NAME_LEN];
+        e.copy_from_slice(b"ANANYA SHARMAIYE");
+        e
+    });
+    assert_eq!(normalize_name(""), [0u8; NAME_LEN]);
+}
+
+#[test]
+fn input_follows_the_circuit_field_order() {
+    let elements = input().to_field_elements();
+    assert_eq!(elements.len(), KYC_MATCH_INPUT_LEN);
+
+    assert_eq!(elements[0], Fq::from(b'A'));
+    assert_eq!(elements[NAME_LEN + 6], Fq::from(b' '));
+    assert_eq!(elements[2 * NAME_LEN], Fq::from(19990412u32));
+    assert_eq!(elements[2 * NAME_LEN + 2], Fq::from(b'L'));
+    assert_eq!(elements[2 * NAME_LEN + 11], Fq::from(b'6'));
+    assert_eq!(elements[KYC_MATCH_INPUT_LEN - 2], Fq::from(b'F'));
+    assert_eq!(
+        elements[KYC_MATCH_INPUT_LEN - 1],
+        Fq::from(0x0f0e0d0c0b0a09080706050403020100u128)
+    );
+}
+
+#[test]
+fn document_commitment_hashes_the_fields_in_order() {
+    // hashlib.sha3_256 over the same 76 bytes
+    assert_eq!(
+        hex::encode(input().document_commitment()),
+        "4989508be924487d81b6f249a309232e201f1744e36d835be1cc6c8c6fb31452"
+    );
+
+    let mut other = input();
+    other.salt += 1;
+    assert_ne!(other.document_commitment(), input().document_commitment());
+}
+
+#[test]
+fn encryption_vectors() {
+    let encrypted = encrypt_input(&input(), &key(ALICE_SECRET), &key(BOB_PUBLIC), NONCE);
+
+    assert_eq!(encrypted.pub_key, key(ALICE_PUBLIC));
+    assert_eq!(encrypted.nonce, NONCE);
+    // from Arcium's own RescueCipher (arcis 0.4.0, built with
+    // `new_with_client_from_key_pair`) under the same keys and nonce
+    assert_eq!(
+        hex::encode(encrypted.ciphertexts[0]),
+        "67abf588c2c618965d989362536d62242d7081a52ba04fa509d86b8962177a58"
+    );
+    assert_eq!(
+        // the PAN's last letter; counter mode leaves it unchanged by the salt after it
+        hex::encode(encrypted.ciphertexts[KYC_MATCH_INPUT_LEN - 2]),
+        "96d51256979da396d022773db602be8e7e44527ce6d46c212f34683f21f0d26f"
+    );
+    let cipher = RescueCipher::new(&key(SHARED_SECRET));
+    let small: Vec<String> = cipher
+        .encrypt(&[Fq::from(0u8), Fq::from(1u8)], NONCE)
+        .iter()
+        .map(hex::encode)
+        .collect();
+    assert_eq!(
+        small,
+        [
+            "26abf588c2c618965d989362536d62242d7081a52ba04fa509d86b8962177a58",
+            "623b2ca92abc6153bc1c5a1a2c2b0ec39ba16b0d2708aa5975b0853dbce80b24",
+        ]
+    );
+
+    // the MXE side, holding Bob's key, reads the input back
+    let mxe = RescueCipher::new(&x25519_shared_secret(&key(BOB_SECRET), &key(ALICE_PUBLIC)));
+    assert_eq!(
+        mxe.decrypt(&encrypted.ciphertexts, NONCE),
+        input().to_field_elements().to_vec()
+    );
+}
+
+#[test]
+fn nonces_change_every_ciphertext() {
+    let a = encrypt_input(&input(), &key(ALICE_SECRET), &key(BOB_PUBLIC), NONCE);
+    let b = encrypt_input(&input(), &key(ALICE_SECRET), &key(BOB_PUBLIC), NONCE + 1);
+    assert!(a.ciphertexts.iter().zip(&b.ciphertexts).all(|(x, y)| x != y));
+}
+
+#[test]
+fn decrypts_a_result_from_the_mxe() {
+    let mxe = RescueCipher::new(&x25519_shared_secret(&key(BOB_SECRET), &key(ALICE_PUBLIC)));
+    let result: [[u8; 32]; 2] = mxe
+        .encrypt(&[Fq::from(0u8), Fq::from(REASON_DOB_MISMATCH)], NONCE)
+        .try_into()
+        .unwrap();
+
+    assert_eq!(
+        decrypt_result(&key(ALICE_SECRET), &key(BOB_PUBLIC), &result, NONCE.to_le_bytes()),
+        Some(KycMatchResult {
+            passed: false,
+            reason: REASON_DOB_MISMATCH,
+        })
+    );
+
+    // any other key reads noise, not a result
+    assert_eq!(
+        decrypt_result(&key(BOB_SECRET), &key(BOB_PUBLIC), &result, NONCE.to_le_bytes()),
+        None
+    );
+}
+
+#[test]
+fn write_kyc_match_input_stages_every_ciphertext_in_order() {
+    let payer = Pubkey::new_unique();
+    let encrypted = encrypt_input(&input(), &key(ALICE_SECRET), &key(BOB_PUBLIC), NONCE);
+    let ixs = instruction::write_kyc_match_input(&payer, 99, &encrypted.ciphertexts);
+
+    assert_eq!(ixs.len(), KYC_MATCH_INPUT_LEN.div_ceil(WRITE_CHUNK));
+    let mut staged = Vec::new();
+    for (i, ix) in ixs.iter().enumerate() {
+        let data: rust::instruction::WriteKycMatchInput = args(ix, "write_kyc_match_input");
+        assert_eq!(data.computation_offset, 99);
+        assert_eq!(data.start as usize, i * WRITE_CHUNK);
+        assert_eq!(ix.accounts[1].pubkey, pda::kyc_match_input(&payer, 99).0);
+        staged.extend(data.ciphertexts);
+    }
+    assert_eq!(staged, encrypted.ciphertexts.to_vec());
+}
+
+#[test]
+fn kyc_match_derives_every_account() {
+    let payer = Pubkey::new_unique();
+    let encrypted = encrypt_input(&input(), &key(ALICE_SECRET), &key(BOB_PUBLIC), NONCE);
+    let attester = Pubkey::new_unique();
+    let recipients = Recipients {
+        attester,
+        attester_nonce: 7,
+        auditor_nonce: 8,
+    };
+    let ix = instruction::kyc_match(&payer, &encrypted, &recipients, 99, 1);
+
+    let data: rust::instruction::KycMatch = args(&ix, "kyc_match");
+    assert_eq!(data.computation_offset, 99);
+    assert_eq!(data.pub_key, key(ALICE_PUBLIC));
+    assert_eq!(data.nonce, NONCE);
+    assert_eq!(data.attester_nonce, 7);
+    assert_eq!(data.auditor_nonce, 8);
+
+    // derived here from Arcium's own helpers and spelled-out seeds, not
+    // through `pda`, in the order of the program's `KycMatch`
+    let program = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &rust::ID).0;
+    let comp_def_offset = arcium_pda::comp_def_offset("kyc_match");
+    let expected = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new(program(&[b"SignerAccount"]), false),
+        AccountMeta::new_readonly(arcium_pda::mxe_acc(&rust::ID), false),
+        AccountMeta::new(arcium_pda::mempool_acc(&rust::ID), false),
+        AccountMeta::new(arcium_pda::execpool_acc(&rust::ID), false),
+        AccountMeta::new(arcium_pda::computation_acc(&rust::ID, 99), false),
+        AccountMeta::new_readonly(
+            arcium_pda::computation_definition_acc(&rust::ID, comp_def_offset),
+            false,
+        ),
+        AccountMeta::new(arcium_pda::cluster_acc(1), false),
+        AccountMeta::new(arcium_pda::FEE_POOL_PDA.0, false),
+        AccountMeta::new_readonly(arcium_pda::CLOCK_PDA.0, false),
+        AccountMeta::new_readonly(
+            program(&[b"kyc_match_input", payer.as_ref(), &99u64.to_le_bytes()]),
+            false,
+        ),
+        AccountMeta::new_readonly(
+            Pubkey::find_program_address(&[b"attesters"], &zord::ID).0,
+            false,
+        ),
+        AccountMeta::new_readonly(program(&[b"attester_key", attester.as_ref()]), false),
+        AccountMeta::new_readonly(program(&[b"auditor_config"]), false),
+        AccountMeta::new_readonly(
+            Pubkey::find_program_address(&[b"kyc", payer.as_ref()], &zord::ID).0,
+            false,
+        ),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(ARCIUM_PROGRAM_ID, false),
+    ];
+    assert_eq!(ix.accounts, expected);
+
+    // every computation has its own computation account and staged input
+    let next = instruction::kyc_match(&payer, &encrypted, &recipients, 100, 1);
+    let changed: Vec<usize> = (0..expected.len())
+        .filter(|&i| next.accounts[i] != ix.accounts[i])
+        .collect();
+    assert_eq!(changed, [5, 10]);
+}
+
+#[test]
+fn reclaim_kyc_match_input_closes_to_the_payer() {
+    let payer = Pubkey::new_unique();
+    let ix = instruction::reclaim_kyc_match_input(&payer, 99);
+
+    let data: rust::instruction::ReclaimKycMatchInput = args(&ix, "reclaim_kyc_match_input");
+    assert_eq!(data.computation_offset, 99);
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(pda::kyc_match_input(&payer, 99).0, false),
+        ]
+    );
+}
+
+#[test]
+fn register_attester_key_uses_zords_allow_list() {
+    let attester = Pubkey::new_unique();
+    let ix = instruction::register_attester_key(&attester, key(BOB_PUBLIC));
+
+    let data: rust::instruction::RegisterAttesterKey = args(&ix, "register_attester_key");
+    assert_eq!(data.x25519_key, key(BOB_PUBLIC));
+
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|m| m.pubkey).collect();
+    assert_eq!(keys[0], attester);
+    assert_eq!(
+        keys[1],
+        Pubkey::find_program_address(&[b"attesters"], &zord::ID).0
+    );
+    assert_eq!(keys[2], pda::attester_key(&attester).0);
+}
@@ -4,23 +4,245 @@ use arcis_imports::*;
 mod circuits {
     use arcis_imports::*;
 
+    // Normalized names: uppercase A-Z and spaces, zero-padded
+    const NAME_LEN: usize = 16;
+    // MRZ passport number, `<`-filled to 9 characters
+    const PASSPORT_NUMBER_LEN: usize = 9;
+    // AAAAA9999A
+    const PAN_LEN: usize = 10;
+    // Bytes hashed into the document commitment: both names, both dates of
+    // birth, passport number and check digit, PAN, salt
+    const COMMITMENT_INPUT_LEN: usize = 2 * NAME_LEN + 2 * 4 + PASSPORT_NUMBER_LEN + 1 + PAN_LEN + 16;
+
+    // Names further apart than this fail outright
+    const MAX_NAME_EDITS: u8 = 2;
+
+    // Score weights; each name edit costs NAME_EDIT_COST. Every weight is
+    // larger than the MAX_NAME_EDITS * NAME_EDIT_COST slack in PASS_SCORE, so
+    // the score only passes when every check holds
+    const DOB_WEIGHT: u8 = 40;
+    const NAME_WEIGHT: u8 = 40;
+    const NAME_EDIT_COST: u8 = 10;
+    const DOC_WEIGHT: u8 = 25;
+    const PASS_SCORE: u8 = DOB_WEIGHT + NAME_WEIGHT + 2 * DOC_WEIGHT - MAX_NAME_EDITS * NAME_EDIT_COST;
+
+    // Reason bits, 0 when the match passed
+    const REASON_DOB_MISMATCH: u8 = 1;
+    const REASON_NAME_MISMATCH: u8 = 2;
+    const REASON_PASSPORT_CHECKSUM: u8 = 4;
+    const REASON_PAN_FORMAT: u8 = 8;
+    const REASON_NOT_ATTESTED: u8 = 16;
+
     pub struct KycMatchInput {
-        pub passport_hash: [u8; 32],
-        pub pan_hash: [u8; 32],
+        pub passport_name: [u8; NAME_LEN],
+        pub pan_name: [u8; NAME_LEN],
+        // YYYYMMDD
+        pub passport_dob: u32,
+        pub pan_dob: u32,
+        pub passport_number: [u8; PASSPORT_NUMBER_LEN],
+        // ASCII digit from the MRZ
+        pub passport_check_digit: u8,
+        pub pan_number: [u8; PAN_LEN],
+        // Chosen by the attester, so the commitment on zord cannot be
+        // brute-forced from guessed documents
+        pub salt: u128,
+    }
+
+    pub struct KycMatchResult {
+        pub passed: bool,
+        pub reason: u8,
     }
 
+    // `commitment_lo` / `commitment_hi` are the little-endian halves of the
+    // document commitment the attester signed into the user's zord
+    // KycAccount; documents hashing to anything else fail the match, so the
+    // payer cannot score documents other than the attested ones.
+    //
+    // Returns the result encrypted to the user, the attester and the auditor,
+    // plus the pass bit in the clear. The callback needs it in the clear: it
+    // becomes `arcium_match` on the user's zord KycAccount, a public flag, so
+    // the reveal discloses nothing the account does not. The score and the
+    // reason never leave the cluster unencrypted.
     #[instruction]
-    pub fn kyc_match(input_ctxt: Enc<Shared, KycMatchInput>) -> Enc<Shared, u8> {
+    pub fn kyc_match(
+        input_ctxt: Enc<Shared, KycMatchInput>,
+        commitment_lo: u128,
+        commitment_hi: u128,
+        attester: Shared,
+        auditor: Shared,
+    ) -> (
+        Enc<Shared, KycMatchResult>,
+        Enc<Shared, KycMatchResult>,
+        Enc<Shared, KycMatchResult>,
+        bool,
+    ) {
         let input = input_ctxt.to_arcis();
-        
-        // Compare the two hashes
-        let is_match = if input.passport_hash == input.pan_hash {
-            1u8
+
+        let dob_ok = input.passport_dob == input.pan_dob;
+        let edits = name_distance(&input.passport_name, &input.pan_name);
+        let name_ok = edits <= MAX_NAME_EDITS;
+        let passport_ok = passport_checksum_ok(&input.passport_number, input.passport_check_digit);
+        let pan_ok = pan_format_ok(&input.pan_number);
+        let attested = commitment_matches(&document_commitment(&input), commitment_lo, commitment_hi);
+
+        let mut score = 0u8;
+        let mut reason = 0u8;
+        if dob_ok {
+            score += DOB_WEIGHT;
+        } else {
+            reason += REASON_DOB_MISMATCH;
+        }
+        if name_ok {
+            score += NAME_WEIGHT - edits * NAME_EDIT_COST;
+        } else {
+            reason += REASON_NAME_MISMATCH;
+        }
+        if passport_ok {
+            score += DOC_WEIGHT;
         } else {
-            0u8
-        };
-        
-        input_ctxt.owner.from_arcis(is_match)
+            reason += REASON_PASSPORT_CHECKSUM;
+        }
+        if pan_ok {
+            score += DOC_WEIGHT;
+        } else {
+            reason += REASON_PAN_FORMAT;
+        }
+        if !attested {
+            reason += REASON_NOT_ATTESTED;
+        }
+
+        let passed = attested & (score >= PASS_SCORE);
+
+        (
+            input_ctxt.owner.from_arcis(KycMatchResult { passed, reason }),
+            attester.from_arcis(KycMatchResult { passed, reason }),
+            auditor.from_arcis(KycMatchResult { passed, reason }),
+            passed.reveal(),
+        )
     }
-}
 
+    // sha3-256 over the fields in order, integers little-endian; the attester
+    // computes the same over the documents it checked
+    fn document_commitment(input: &KycMatchInput) -> [u8; 32] {
+        let mut bytes = [0u8; COMMITMENT_INPUT_LEN];
+        for i in 0..NAME_LEN {
+            bytes[i] = input.passport_name[i];
+            bytes[NAME_LEN + i] = input.pan_name[i];
+        }
+        let dob_at = 2 * NAME_LEN;
+        for i in 0..4 {
+            bytes[dob_at + i] = (input.passport_dob >> (8 * i)) as u8;
+            bytes[dob_at + 4 + i] = (input.pan_dob >> (8 * i)) as u8;
+        }
+        let passport_at = dob_at + 8;
+        for i in 0..PASSPORT_NUMBER_LEN {
+            bytes[passport_at + i] = input.passport_number[i];
+        }
+        bytes[passport_at + PASSPORT_NUMBER_LEN] = input.passport_check_digit;
+        let pan_at = passport_at + PASSPORT_NUMBER_LEN + 1;
+        for i in 0..PAN_LEN {
+            bytes[pan_at + i] = input.pan_number[i];
+        }
+        let salt_at = pan_at + PAN_LEN;
+        for i in 0..16 {
+            bytes[salt_at + i] = (input.salt >> (8 * i)) as u8;
+        }
+        SHA3_256::new().digest(&bytes)
+    }
+
+    fn commitment_matches(digest: &[u8; 32], lo: u128, hi: u128) -> bool {
+        let mut ok = true;
+        for i in 0..16 {
+            ok = ok & (digest[i] == (lo >> (8 * i)) as u8) & (digest[16 + i] == (hi >> (8 * i)) as u8);
+        }
+        ok
+    }
+
+    // Levenshtein distance of the unpadded names, capped at MAX_NAME_EDITS + 1
+    fn name_distance(a: &[u8; NAME_LEN], b: &[u8; NAME_LEN]) -> u8 {
+        let mut len_a = 0u8;
+        let mut len_b = 0u8;
+        for i in 0..NAME_LEN {
+            if a[i] != 0 {
+                len_a += 1;
+            }
+            if b[i] != 0 {
+                len_b += 1;
+            }
+        }
+
+        // Full table over the padded names; the answer sits at (len_a, len_b),
+        // picked without branching on the secret lengths
+        let mut prev = [0u8; NAME_LEN + 1];
+        for j in 0..=NAME_LEN {
+            prev[j] = j as u8;
+        }
+        let mut distance = if len_a == 0 { len_b } else { 0u8 };
+
+        for i in 1..=NAME_LEN {
+            let mut cur = [0u8; NAME_LEN + 1];
+            cur[0] = i as u8;
+            for j in 1..=NAME_LEN {
+                let substitute = if a[i - 1] == b[j - 1] { prev[j - 1] } else { prev[j - 1] + 1 };
+                let delete = prev[j] + 1;
+                let insert = cur[j - 1] + 1;
+                cur[j] = min(min(substitute, delete), insert);
+            }
+            for j in 0..=NAME_LEN {
+                if (len_a == i as u8) & (len_b == j as u8) {
+                    distance = cur[j];
+                }
+            }
+            prev = cur;
+        }
+
+        min(distance, MAX_NAME_EDITS + 1)
+    }
+
+    fn min(a: u8, b: u8) -> u8 {
+        if a < b {
+            a
+        } else {
+            b
+        }
+    }
+
+    // ICAO 9303 check digit: weights 7, 3, 1 over digit / letter / `<` values, mod 10
+    fn passport_checksum_ok(number: &[u8; PASSPORT_NUMBER_LEN], check_digit: u8) -> bool {
+        let weights = [7u16, 3, 1];
+        let mut sum = 0u16;
+        for i in 0..PASSPORT_NUMBER_LEN {
+            sum += mrz_value(number[i]) * weights[i % 3];
+        }
+        (sum % 10) as u8 + b'0' == check_digit
+    }
+
+    fn mrz_value(c: u8) -> u16 {
+        if is_digit(c) {
+            (c - b'0') as u16
+        } else if is_letter(c) {
+            (c - b'A') as u16 + 10
+        } else {
+            // `<` filler
+            0
+        }
+    }
+
+    // Five letters, the fourth `P` for an individual, four digits, one letter
+    fn pan_format_ok(pan: &[u8; PAN_LEN]) -> bool {
+        let mut ok = pan[3] == b'P';
+        for i in 0..PAN_LEN {
+            let valid = if i < 5 || i == 9 { is_letter(pan[i]) } else { is_digit(pan[i]) };
+            ok = ok & valid;
+        }
+        ok
+    }
+
+    fn is_digit(c: u8) -> bool {
+        (c >= b'0') & (c <= b'9')
+    }
+
+    fn is_letter(c: u8) -> bool {
+        (c >= b'A') & (c <= b'Z')
+    }
+}
@@ -1,8 +1,20 @@
 use anchor_lang::prelude::*;
 use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+use zord::program::Zord;
+use zord::state::attester_config::AttesterConfig;
+use zord::state::kyc_account::{KycAccount, KycStatus};
+use crate::program::Rust;
 
 declare_id!("6NK6Cejzcj2bJMTSQ2WAhaazQnuDUijESQmW2nWhkNDG");
 
+const COMP_DEF_OFFSET_KYC_MATCH: u32 = comp_def_offset("kyc_match");
+
+// One ciphertext per field element of `KycMatchInput`: both names (16 + 16),
+// both dates of birth (1 + 1), passport number and check digit (9 + 1), PAN (10),
+// salt of the document commitment (1)
+pub const KYC_MATCH_INPUT_LEN: usize = 55;
+
 //
 // ARCIUM PROGRAM MODULE
 //
@@ -19,33 +31,151 @@ pub mod rust {
         Ok(())
     }
 
+    // -----------------------------------------------------------
+    //  Auditor key
+    // -----------------------------------------------------------
+    // Every kyc_match result is also encrypted to this x25519 key; only the
+    // upgrade authority can set it, and becomes the admin allowed to rotate it
+    pub fn init_auditor_config(ctx: Context<InitAuditorConfig>, auditor: [u8; 32]) -> Result<()> {
+        let config = &mut ctx.accounts.auditor_config;
+        config.admin = ctx.accounts.admin.key();
+        config.auditor = auditor;
+        config.bump = ctx.bumps.auditor_config;
+
+        emit!(AuditorRotated {
+            old_auditor: [0u8; 32],
+            new_auditor: auditor,
+        });
+
+        Ok(())
+    }
+
+    // Computations already queued still encrypt to the key they were queued with
+    pub fn rotate_auditor(ctx: Context<RotateAuditor>, auditor: [u8; 32]) -> Result<()> {
+        let config = &mut ctx.accounts.auditor_config;
+        let old_auditor = config.auditor;
+        config.auditor = auditor;
+
+        emit!(AuditorRotated {
+            old_auditor,
+            new_auditor: auditor,
+        });
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------
+    //  Attester key
+    // -----------------------------------------------------------
+    // An attester on zord's allow-list publishes the x25519 key its copy of
+    // each kyc_match result is encrypted to; calling again replaces it
+    pub fn register_attester_key(
+        ctx: Context<RegisterAttesterKey>,
+        x25519_key: [u8; 32],
+    ) -> Result<()> {
+        let key = &mut ctx.accounts.attester_key;
+        key.attester = ctx.accounts.attester.key();
+        key.x25519_key = x25519_key;
+        key.bump = ctx.bumps.attester_key;
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------
+    //  Stage encrypted input
+    // -----------------------------------------------------------
+    // 55 ciphertexts are 1.7 KiB, more than one transaction carries, so the
+    // client writes them here in chunks before calling `kyc_match` with the
+    // same computation offset. The callback closes the account, or the payer
+    // with `reclaim_kyc_match_input`.
+    pub fn write_kyc_match_input(
+        ctx: Context<WriteKycMatchInput>,
+        computation_offset: u64,
+        start: u8,
+        ciphertexts: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let start = start as usize;
+        let end = start
+            .checked_add(ciphertexts.len())
+            .ok_or(ArciumError::InputOutOfRange)?;
+        require!(end <= KYC_MATCH_INPUT_LEN, ArciumError::InputOutOfRange);
+
+        let input = &mut ctx.accounts.kyc_match_input;
+        input.bump = ctx.bumps.kyc_match_input;
+        input.computation_offset = computation_offset;
+        input.ciphertexts[start..end].copy_from_slice(&ciphertexts);
+
+        Ok(())
+    }
+
+    // The payer takes back the rent of an input no callback closed, e.g. one
+    // that was never queued. Reclaiming a queued input only fails the payer's
+    // own computation, whose callback then finds no input
+    pub fn reclaim_kyc_match_input(
+        _ctx: Context<ReclaimKycMatchInput>,
+        _computation_offset: u64,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     // -----------------------------------------------------------
     //  Invoke encrypted computation
     // -----------------------------------------------------------
     pub fn kyc_match(
         ctx: Context<KycMatch>,
         computation_offset: u64,
-        ciphertext_passport_hash: [u8; 32],
-        ciphertext_pan_hash: [u8; 32],
         pub_key: [u8; 32],
         nonce: u128,
+        attester_nonce: u128,
+        auditor_nonce: u128,
     ) -> Result<()> {
         // Required Arcium pattern
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
+        let commitment = ctx.accounts.kyc_account.document_commitment;
+        let (commitment_lo, commitment_hi) = commitment.split_at(16);
+
+        // The nodes read the staged ciphertexts straight from the account
         let args = vec![
             Argument::ArcisPubkey(pub_key),
             Argument::PlaintextU128(nonce),
-            Argument::EncryptedU8(ciphertext_passport_hash),
-            Argument::EncryptedU8(ciphertext_pan_hash),
+            Argument::Account(
+                ctx.accounts.kyc_match_input.key(),
+                KycMatchInputAccount::CIPHERTEXTS_OFFSET,
+                KycMatchInputAccount::CIPHERTEXTS_LEN,
+            ),
+            // the input must hash to the document commitment the attester
+            // signed into the payer's KycAccount
+            Argument::PlaintextU128(u128::from_le_bytes(commitment_lo.try_into().unwrap())),
+            Argument::PlaintextU128(u128::from_le_bytes(commitment_hi.try_into().unwrap())),
+            // the result is also encrypted to the x25519 key of an attester
+            // on zord's allow-list
+            Argument::ArcisPubkey(ctx.accounts.attester_key.x25519_key),
+            Argument::PlaintextU128(attester_nonce),
+            // and to the auditor's, which the caller cannot choose
+            Argument::ArcisPubkey(ctx.accounts.auditor_config.auditor),
+            Argument::PlaintextU128(auditor_nonce),
         ];
 
+        // The callback writes the result into the payer's zord KycAccount,
+        // signing the CPI with this program's `zord_authority` PDA, and
+        // refunds the staged input to the payer
+        let (zord_authority, _) = Pubkey::find_program_address(&[b"zord_authority"], &ID);
+        let kyc_account = ctx.accounts.kyc_account.key();
+
         queue_computation(
             ctx.accounts,
             computation_offset,
             args,
             None,
-            vec![KycMatchCallback::callback_ix(&[])],
+            vec![KycMatchCallback::callback_ix(&[
+                CallbackAccount { pubkey: zord_authority, is_writable: false },
+                CallbackAccount { pubkey: kyc_account, is_writable: true },
+                CallbackAccount { pubkey: zord::ID, is_writable: false },
+                CallbackAccount { pubkey: ctx.accounts.kyc_match_input.key(), is_writable: true },
+                CallbackAccount { pubkey: ctx.accounts.payer.key(), is_writable: true },
+                CallbackAccount { pubkey: ctx.accounts.attester_key.attester, is_writable: false },
+            ])],
             1,
         )?;
 
@@ -60,14 +190,59 @@ pub mod rust {
         ctx: Context<KycMatchCallback>,
         output: ComputationOutputs<KycMatchOutput>,
     ) -> Result<()> {
-        let o = match output {
-            ComputationOutputs::Success(KycMatchOutput { field_0 }) => field_0,
-            _ => return err!(ArciumError::AbortedComputation),
+        let user = ctx.accounts.kyc_account.user;
+
+        // An aborted computation leaves zord untouched; the event is the
+        // only trace of it, so clients waiting on the result can stop.
+        // Either way the staged input is closed on return
+        let (result, attester_result, auditor_result, matched) = match output {
+            ComputationOutputs::Success(KycMatchOutput {
+                field_0:
+                    KycMatchOutputStruct0 {
+                        field_0: result,
+                        field_1: attester_result,
+                        field_2: auditor_result,
+                        field_3: matched,
+                    },
+            }) => (result, attester_result, auditor_result, matched),
+            _ => {
+                emit!(KycMatchAborted { user });
+                return Ok(());
+            }
         };
 
+        // `kyc_match` checked the record, but it may have been revoked since;
+        // zord would refuse the CPI and the input would never be closed
+        if ctx.accounts.kyc_account.status == KycStatus::Revoked {
+            emit!(KycMatchAborted { user });
+            return Ok(());
+        }
+
+        // Store the revealed pass bit on the user's zord KycAccount
+        let bump = ctx.bumps.zord_authority;
+        zord::cpi::record_arcium_match(
+            CpiContext::new_with_signer(
+                ctx.accounts.zord_program.to_account_info(),
+                zord::cpi::accounts::RecordArciumMatch {
+                    arcium_authority: ctx.accounts.zord_authority.to_account_info(),
+                    kyc_account: ctx.accounts.kyc_account.to_account_info(),
+                },
+                &[&[b"zord_authority", &[bump]]],
+            ),
+            matched,
+        )?;
+
         emit!(KycMatchEvent {
-            result: o.ciphertexts[0],
-            nonce: o.nonce.to_le_bytes(),
+            user,
+            attester: ctx.accounts.attester.key(),
+            matched,
+            result: result.ciphertexts,
+            nonce: result.nonce.to_le_bytes(),
+            attester_result: attester_result.ciphertexts,
+            attester_nonce: attester_result.nonce.to_le_bytes(),
+            auditor: auditor_result.encryption_key,
+            auditor_result: auditor_result.ciphertexts,
+            auditor_nonce: auditor_result.nonce.to_le_bytes(),
         });
 
         Ok(())
@@ -101,11 +276,371 @@ pub struct InitKycMatchCompDef<'info> {
 }
 
 
+//
+//  AUDITOR CONFIG ACCOUNTS
+//
+#[derive(Accounts)]
+pub struct InitAuditorConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AuditorConfig::SIZE,
+        seeds = [b"auditor_config"],
+        bump
+    )]
+    pub auditor_config: Account<'info, AuditorConfig>,
+
+    // only the upgrade authority can claim the singleton config, like zord's
+    // initialize_verifier
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ ArciumError::NotUpgradeAuthority
+    )]
+    pub program: Program<'info, Rust>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ArciumError::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RotateAuditor<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"auditor_config"],
+        bump = auditor_config.bump,
+        has_one = admin @ ArciumError::Unauthorized
+    )]
+    pub auditor_config: Account<'info, AuditorConfig>,
+}
+
+
+//
+//  ATTESTER KEY ACCOUNTS
+//
+#[derive(Accounts)]
+pub struct RegisterAttesterKey<'info> {
+    #[account(mut)]
+    pub attester: Signer<'info>,
+
+    #[account(
+        seeds = [b"attesters"],
+        bump = attester_config.bump,
+        seeds::program = zord::ID,
+        constraint = attester_config.is_attester(&attester.key()) @ ArciumError::UnknownAttester
+    )]
+    pub attester_config: Box<Account<'info, AttesterConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = attester,
+        space = 8 + AttesterKey::SIZE,
+        seeds = [b"attester_key", attester.key().as_ref()],
+        bump
+    )]
+    pub attester_key: Account<'info, AttesterKey>,
+
+    pub system_program: Program<'info, System>,
+}
+
+
+//
+//  INPUT STAGING ACCOUNTS
+//
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct WriteKycMatchInput<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // One per computation, so a second kyc_match cannot overwrite the input
+    // of one the nodes have not read yet
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + KycMatchInputAccount::SIZE,
+        seeds = [b"kyc_match_input", payer.key().as_ref(), &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub kyc_match_input: Box<Account<'info, KycMatchInputAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct ReclaimKycMatchInput<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"kyc_match_input", payer.key().as_ref(), &computation_offset.to_le_bytes()],
+        bump = kyc_match_input.bump
+    )]
+    pub kyc_match_input: Box<Account<'info, KycMatchInputAccount>>,
+}
+
+
+//
+//  QUEUE ACCOUNTS
+//
+#[queue_computation_accounts("kyc_match", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct KycMatch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+    /// CHECK: mempool_account, checked by the arcium program
+    #[account(mut, address = derive_mempool_pda!())]
+    pub mempool_account: UncheckedAccount<'info>,
+
+    /// CHECK: executing_pool, checked by the arcium program
+    #[account(mut, address = derive_execpool_pda!())]
+    pub executing_pool: UncheckedAccount<'info>,
+
+    /// CHECK: computation_account, checked by the arcium program
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    pub computation_account: UncheckedAccount<'info>,
+
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_KYC_MATCH))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account, ArciumError::ClusterNotSet)
+    )]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+
+    // Staged by `write_kyc_match_input`, read by the nodes, not by this program
+    #[account(
+        seeds = [b"kyc_match_input", payer.key().as_ref(), &computation_offset.to_le_bytes()],
+        bump = kyc_match_input.bump
+    )]
+    pub kyc_match_input: Box<Account<'info, KycMatchInputAccount>>,
+
+    #[account(
+        seeds = [b"attesters"],
+        bump = attester_config.bump,
+        seeds::program = zord::ID
+    )]
+    pub attester_config: Box<Account<'info, AttesterConfig>>,
+
+    // Registered through `register_attester_key`, and still on zord's allow-list
+    #[account(
+        seeds = [b"attester_key", attester_key.attester.as_ref()],
+        bump = attester_key.bump,
+        constraint = attester_config.is_attester(&attester_key.attester) @ ArciumError::UnknownAttester
+    )]
+    pub attester_key: Box<Account<'info, AttesterKey>>,
+
+    #[account(seeds = [b"auditor_config"], bump = auditor_config.bump)]
+    pub auditor_config: Box<Account<'info, AuditorConfig>>,
+
+    // The callback records into this record; were it missing or revoked,
+    // zord would refuse the CPI and the staged input would never be closed.
+    // Its document commitment is what the input is checked against
+    #[account(
+        seeds = [b"kyc", payer.key().as_ref()],
+        bump,
+        seeds::program = zord::ID,
+        constraint = kyc_account.status != KycStatus::Revoked @ ArciumError::KycRevoked,
+        constraint = kyc_account.document_commitment != [0u8; 32]
+            @ ArciumError::NoDocumentCommitment
+    )]
+    pub kyc_account: Box<Account<'info, KycAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+
+//
+//  CALLBACK ACCOUNTS
+//
+// The last six accounts are the extra callback accounts queued by `kyc_match`, in order.
+#[callback_accounts("kyc_match")]
+#[derive(Accounts)]
+pub struct KycMatchCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_KYC_MATCH))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+    /// CHECK: instructions sysvar, checked by the address constraint
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// CHECK: data-less PDA, only signs `zord::record_arcium_match`
+    #[account(seeds = [b"zord_authority"], bump)]
+    pub zord_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"kyc", kyc_account.user.as_ref()],
+        bump,
+        seeds::program = zord::ID
+    )]
+    pub kyc_account: Box<Account<'info, KycAccount>>,
+
+    pub zord_program: Program<'info, Zord>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            b"kyc_match_input",
+            payer.key().as_ref(),
+            &kyc_match_input.computation_offset.to_le_bytes(),
+        ],
+        bump = kyc_match_input.bump
+    )]
+    pub kyc_match_input: Box<Account<'info, KycMatchInputAccount>>,
+
+    /// CHECK: gets the staging rent back, pinned by the `kyc_match_input` seeds
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: the attester the result was encrypted to, only reported in the event
+    pub attester: UncheckedAccount<'info>,
+}
+
+
+// -----------------------------------------------------------
+//  STATE
+// -----------------------------------------------------------
+#[account]
+pub struct AuditorConfig {
+    pub admin: Pubkey,
+    // x25519 public key, not a Solana key
+    pub auditor: [u8; 32],
+    pub bump: u8,
+}
+
+impl AuditorConfig {
+    pub const SIZE: usize =
+        32 +    // admin
+        32 +    // auditor
+        1;      // bump
+}
+
+#[account]
+pub struct AttesterKey {
+    // a zord attester's Solana key
+    pub attester: Pubkey,
+    // x25519 public key, not a Solana key
+    pub x25519_key: [u8; 32],
+    pub bump: u8,
+}
+
+impl AttesterKey {
+    pub const SIZE: usize =
+        32 +    // attester
+        32 +    // x25519_key
+        1;      // bump
+}
+
+#[account]
+pub struct KycMatchInputAccount {
+    pub bump: u8,
+    // the callback re-derives the account from it before closing it
+    pub computation_offset: u64,
+    pub ciphertexts: [[u8; 32]; KYC_MATCH_INPUT_LEN],
+}
+
+impl KycMatchInputAccount {
+    pub const SIZE: usize =
+        1  +    // bump
+        8  +    // computation_offset
+        32 * KYC_MATCH_INPUT_LEN; // ciphertexts
+
+    // discriminator + bump + computation_offset
+    pub const CIPHERTEXTS_OFFSET: u32 = 8 + 1 + 8;
+    pub const CIPHERTEXTS_LEN: u32 = 32 * KYC_MATCH_INPUT_LEN as u32;
+}
+
+
+// -----------------------------------------------------------
+//  EVENTS
+// -----------------------------------------------------------
+#[event]
+pub struct KycMatchEvent {
+    pub user: Pubkey,
+    // whose `AttesterKey` `attester_result` is encrypted to
+    pub attester: Pubkey,
+    // revealed by the circuit and recorded as `KycAccount.arcium_match`
+    pub matched: bool,
+    // `KycMatchResult { passed, reason }` encrypted to the user's key
+    pub result: [[u8; 32]; 2],
+    pub nonce: [u8; 16],
+    // the same result encrypted to the attester's x25519 key
+    pub attester_result: [[u8; 32]; 2],
+    pub attester_nonce: [u8; 16],
+    // and to the auditor key from `AuditorConfig` at queue time
+    pub auditor: [u8; 32],
+    pub auditor_result: [[u8; 32]; 2],
+    pub auditor_nonce: [u8; 16],
+}
+
+#[event]
+pub struct AuditorRotated {
+    pub old_auditor: [u8; 32],
+    pub new_auditor: [u8; 32],
+}
+
+#[event]
+pub struct KycMatchAborted {
+    pub user: Pubkey,
+}
+
+
 // -----------------------------------------------------------
 //  ERRORS
 // -----------------------------------------------------------
 #[error_code]
 pub enum ArciumError {
-    #[msg("Computation was aborted")]
-    AbortedComputation,
+    #[msg("Cluster not set")]
+    ClusterNotSet,
+    #[msg("Ciphertexts fall outside the kyc_match input")]
+    InputOutOfRange,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Attester is not on zord's allow-list")]
+    UnknownAttester,
+    #[msg("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+    #[msg("The payer's zord KYC record is revoked")]
+    KycRevoked,
+    #[msg("The payer's zord KYC record has no attested document commitment")]
+    NoDocumentCommitment,
 }
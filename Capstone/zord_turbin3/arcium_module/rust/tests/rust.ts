@@ -1,81 +1,431 @@
 import * as anchor from "@coral-xyz/anchor";
-import { ArciumClient } from "@arcium/arcium-js";
+import { Program } from "@coral-xyz/anchor";
+import { Ed25519Program, PublicKey } from "@solana/web3.js";
+import {
+  awaitComputationFinalization,
+  buildFinalizeCompDefTx,
+  deserializeLE,
+  getArciumAccountBaseSeed,
+  getArciumEnv,
+  getArciumProgAddress,
+  getClusterAccAddress,
+  getCompDefAccAddress,
+  getCompDefAccOffset,
+  getComputationAccAddress,
+  getExecutingPoolAccAddress,
+  getMempoolAccAddress,
+  getMXEAccAddress,
+  getMXEPublicKey,
+  RescueCipher,
+  x25519,
+} from "@arcium-hq/client";
+import { createHash, randomBytes } from "crypto";
+import fs from "fs";
+import path from "path";
 import { expect } from "chai";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
 
-describe("Arcium AddTogether Test", () => {
-    const provider = anchor.AnchorProvider.local();
-    anchor.setProvider(provider);
+// zord is loaded at genesis (see Anchor.toml), its IDL comes from its own build
+const ZORD_IDL = path.join(__dirname, "../../../zord/target/idl/zord.json");
 
-    const program = anchor.workspace.Rust;   // your arcium anchor program
-    const wallet = provider.wallet as anchor.Wallet;
+const BPF_LOADER_UPGRADEABLE = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
 
-    let arcium: ArciumClient;
+// Ciphertexts per `write_kyc_match_input`, small enough for one transaction
+const WRITE_CHUNK = 16;
 
-    before(async () => {
-        arcium = await ArciumClient.connect(
-            provider.connection,
-            wallet.payer,
-            { cluster: "localnet" }
-        );
-    });
+const NAME_LEN = 16;
+// ICAO 9303 specimen passport number and its check digit
+const PASSPORT_NUMBER = "L898902C3";
+const PASSPORT_CHECK_DIGIT = "6";
+const PAN_NUMBER = "ABCPE1234F";
+
+const REASON_DOB_MISMATCH = 1;
+const REASON_PASSPORT_CHECKSUM = 4;
+const REASON_NOT_ATTESTED = 16;
+
+// zord `Attestation` layout version and document_flags
+const ATTESTATION_VERSION = 2;
+const DOC_PASSPORT_AND_PAN = 3;
+
+// `checkDigit` is the passport's MRZ check digit, PASSPORT_CHECK_DIGIT unless
+// a test breaks it
+type Document = { name: string; dob: number; checkDigit?: string };
+
+// `KycMatchInput` field order, one field element per ciphertext
+function encodeInput(passport: Document, pan: Document, salt: bigint): bigint[] {
+  const ascii = (s: string, len: number) =>
+    Array.from(Buffer.from(s.padEnd(len, "\0"), "ascii")).map((b) => BigInt(b));
+  return [
+    ...ascii(passport.name, NAME_LEN),
+    ...ascii(pan.name, NAME_LEN),
+    BigInt(passport.dob),
+    BigInt(pan.dob),
+    ...ascii(PASSPORT_NUMBER, PASSPORT_NUMBER.length),
+    ...ascii(passport.checkDigit ?? PASSPORT_CHECK_DIGIT, 1),
+    ...ascii(PAN_NUMBER, PAN_NUMBER.length),
+    salt,
+  ];
+}
+
+// sha3-256 over the same fields, integers little-endian: what the attester
+// signs into zord and the circuit checks the input against
+function documentCommitment(passport: Document, pan: Document, salt: bigint): Buffer {
+  const u32 = (n: number) => {
+    const b = Buffer.alloc(4);
+    b.writeUInt32LE(n);
+    return b;
+  };
+  const salt16 = Buffer.alloc(16);
+  salt16.writeBigUInt64LE(salt & BigInt("0xffffffffffffffff"));
+  salt16.writeBigUInt64LE(salt >> BigInt(64), 8);
+  return createHash("sha3-256")
+    .update(
+      Buffer.concat([
+        Buffer.from(passport.name.padEnd(NAME_LEN, "\0"), "ascii"),
+        Buffer.from(pan.name.padEnd(NAME_LEN, "\0"), "ascii"),
+        u32(passport.dob),
+        u32(pan.dob),
+        Buffer.from(PASSPORT_NUMBER, "ascii"),
+        Buffer.from(passport.checkDigit ?? PASSPORT_CHECK_DIGIT, "ascii"),
+        Buffer.from(PAN_NUMBER, "ascii"),
+        salt16,
+      ])
+    )
+    .digest();
+}
+
+describe("kyc_match", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const program = anchor.workspace.Rust as Program;
+  const zord = new Program(JSON.parse(fs.readFileSync(ZORD_IDL, "utf8")), provider);
+  const payer = provider.wallet.publicKey;
+  const arciumEnv = getArciumEnv();
+
+  const compDefOffset = Buffer.from(getCompDefAccOffset("kyc_match")).readUInt32LE();
+  const [kycAccount] = PublicKey.findProgramAddressSync(
+    [Buffer.from("kyc"), payer.toBuffer()],
+    zord.programId
+  );
+
+  let cipher: RescueCipher;
+  let publicKey: Uint8Array;
+  let attesterCipher: RescueCipher;
+  let attesterPublicKey: Uint8Array;
+  let mxePublicKey: Uint8Array;
+  let auditorCipher: RescueCipher;
+
+  // Fresh x25519 key and its cipher with the MXE
+  const newRecipient = () => {
+    const privateKey = x25519.utils.randomSecretKey();
+    return {
+      publicKey: x25519.getPublicKey(privateKey),
+      cipher: new RescueCipher(x25519.getSharedSecret(privateKey, mxePublicKey)),
+    };
+  };
 
-    it("Runs add_together computation", async () => {
-        // Values encrypted externally — use dummy encryptions for test
-        const ciphertext0 = new Array(32).fill(1);
-        const ciphertext1 = new Array(32).fill(2);
-
-        const pubKey = new Array(32).fill(9);
-        const nonce = BigInt(1234);
-
-        // 1️⃣ Init computation definition once
-        await program.methods
-            .initAddTogetherCompDef()
-            .accounts({
-                payer: wallet.publicKey,
-                mxeAccount: arcium.mxePda,
-                compDefAccount: arcium.compDefPda(program.programId, "add_together"),
-                arciumProgram: arcium.programId,
-                systemProgram: SystemProgram.programId
-            })
-            .rpc();
-
-        // Compute offset = 0 for testing
-        const compOffset = new anchor.BN(0);
-
-        // 2️⃣ Queue the computation
-        const tx = await program.methods
-            .addTogether(
-                compOffset,
-                ciphertext0,
-                ciphertext1,
-                pubKey,
-                nonce
-            )
-            .accounts({
-                payer: wallet.publicKey,
-                signPdaAccount: arcium.signerPda(wallet.publicKey),
-                mxeAccount: arcium.mxePda,
-                mempoolAccount: arcium.mempoolPda,
-                executingPool: arcium.execPoolPda,
-                computationAccount: arcium.computationPda(compOffset),
-                compDefAccount: arcium.compDefPda(program.programId, "add_together"),
-                clusterAccount: arcium.clusterPda,
-                poolAccount: arcium.feePoolPda,
-                clockAccount: arcium.clockPda,
-                arciumProgram: arcium.programId,
-                systemProgram: SystemProgram.programId
-            })
-            .rpc();
-
-        console.log("Queued computation:", tx);
-
-        // 3️⃣ Wait for Arcium executor to compute result
-        const result = await arcium.waitForComputation(program.programId, compOffset);
-
-        console.log("Arcium Output:", result);
-
-        expect(result.success).to.be.true;
-        expect(result.output.field_0.ciphertexts).to.have.length(1);
+  const awaitEvent = async (name: string): Promise<any> => {
+    let listener: number;
+    const event = await new Promise((res) => {
+      listener = program.addEventListener(name, res);
     });
+    await program.removeEventListener(listener);
+    return event;
+  };
+
+  before(async () => {
+    // 1. Computation definition, finalized with the circuit from `arcium build`
+    const [compDefPda] = PublicKey.findProgramAddressSync(
+      [
+        getArciumAccountBaseSeed("ComputationDefinitionAccount"),
+        program.programId.toBuffer(),
+        getCompDefAccOffset("kyc_match"),
+      ],
+      getArciumProgAddress()
+    );
+    await program.methods
+      .initKycMatchCompDef()
+      .accounts({
+        payer,
+        mxeAccount: getMXEAccAddress(program.programId),
+        compDefAccount: compDefPda,
+      })
+      .rpc({ commitment: "confirmed" });
+    const finalizeTx = await buildFinalizeCompDefTx(provider, compDefOffset, program.programId);
+    await provider.sendAndConfirm(finalizeTx);
+
+    // 2. The callback records into the payer's zord KycAccount, and the payer
+    //    doubles as the zord attester the result is also encrypted to and that
+    //    attests the documents of each run
+    await zord.methods.initializeKyc().accounts({ user: payer }).rpc({ commitment: "confirmed" });
+    const [programData] = PublicKey.findProgramAddressSync(
+      [zord.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE
+    );
+    await zord.methods
+      .initializeAttesters()
+      .accounts({ admin: payer, program: zord.programId, programData })
+      .rpc({ commitment: "confirmed" });
+    await zord.methods.addAttester(payer).accounts({ admin: payer }).rpc({ commitment: "confirmed" });
+
+    // 3. Shared secrets with the MXE, for the user, the attester and the auditor
+    while (!mxePublicKey) {
+      mxePublicKey = await getMXEPublicKey(provider, program.programId);
+    }
+    ({ publicKey, cipher } = newRecipient());
+    ({ publicKey: attesterPublicKey, cipher: attesterCipher } = newRecipient());
+    await program.methods
+      .registerAttesterKey(Array.from(attesterPublicKey))
+      .accounts({ attester: payer })
+      .rpc({ commitment: "confirmed" });
+    const auditor = newRecipient();
+    auditorCipher = auditor.cipher;
+    await program.methods
+      .initAuditorConfig(Array.from(auditor.publicKey))
+      .accounts({
+        admin: payer,
+        program: program.programId,
+        programData: PublicKey.findProgramAddressSync(
+          [program.programId.toBuffer()],
+          BPF_LOADER_UPGRADEABLE
+        )[0],
+      })
+      .rpc({ commitment: "confirmed" });
+  });
+
+  // The payer, as zord attester, attests its own record with `documentCommitment`
+  async function attest(commitment: Buffer) {
+    const attester = (provider.wallet as anchor.Wallet).payer;
+    const now = Math.floor(Date.now() / 1000);
+    const attestation = {
+      version: ATTESTATION_VERSION,
+      subject: payer,
+      issuer: payer,
+      issuedAt: new anchor.BN(now - 60),
+      expiresAt: new anchor.BN(now + 365 * 24 * 60 * 60),
+      documentFlags: DOC_PASSPORT_AND_PAN,
+      claimsCommitment: Array.from(Buffer.alloc(32)),
+      documentCommitment: Array.from(commitment),
+    };
+    // sha256 of its Borsh encoding, signed as `user || hash`
+    const i64 = (n: anchor.BN) => {
+      const b = Buffer.alloc(8);
+      b.writeBigInt64LE(BigInt(n.toString()));
+      return b;
+    };
+    const hash = createHash("sha256")
+      .update(
+        Buffer.concat([
+          Buffer.from([attestation.version]),
+          payer.toBuffer(),
+          payer.toBuffer(),
+          i64(attestation.issuedAt),
+          i64(attestation.expiresAt),
+          Buffer.from([attestation.documentFlags]),
+          Buffer.from(attestation.claimsCommitment),
+          commitment,
+        ])
+      )
+      .digest();
+    await zord.methods
+      .submitAttestation(attestation)
+      .accounts({ user: payer })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: attester.secretKey,
+          message: Buffer.concat([payer.toBuffer(), hash]),
+        }),
+      ])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  // Attests `attested` (the documents themselves unless a test swaps them),
+  // encrypts both documents, stages them and queues kyc_match; resolves with
+  // the event
+  async function runKycMatch(
+    passport: Document,
+    pan: Document,
+    attested: [Document, Document] = [passport, pan]
+  ): Promise<any> {
+    const salt = BigInt("0x" + randomBytes(16).toString("hex"));
+    await attest(documentCommitment(...attested, salt));
+
+    const nonce = randomBytes(16);
+    const ciphertexts = cipher.encrypt(encodeInput(passport, pan, salt), nonce);
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    const [kycMatchInput] = PublicKey.findProgramAddressSync(
+      [Buffer.from("kyc_match_input"), payer.toBuffer(), computationOffset.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    for (let start = 0; start < ciphertexts.length; start += WRITE_CHUNK) {
+      await program.methods
+        .writeKycMatchInput(
+          computationOffset,
+          start,
+          ciphertexts.slice(start, start + WRITE_CHUNK).map((c) => Array.from(c))
+        )
+        .accountsPartial({ payer, kycMatchInput })
+        .rpc({ commitment: "confirmed" });
+    }
+
+    const eventPromise = awaitEvent("kycMatchEvent");
+    await program.methods
+      .kycMatch(
+        computationOffset,
+        Array.from(publicKey),
+        new anchor.BN(deserializeLE(nonce).toString()),
+        new anchor.BN(deserializeLE(randomBytes(16)).toString()),
+        new anchor.BN(deserializeLE(randomBytes(16)).toString())
+      )
+      .accountsPartial({
+        payer,
+        computationAccount: getComputationAccAddress(program.programId, computationOffset),
+        clusterAccount: getClusterAccAddress(arciumEnv.arciumClusterOffset),
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(program.programId, compDefOffset),
+        kycMatchInput,
+        attesterConfig: PublicKey.findProgramAddressSync([Buffer.from("attesters")], zord.programId)[0],
+        attesterKey: PublicKey.findProgramAddressSync(
+          [Buffer.from("attester_key"), payer.toBuffer()],
+          program.programId
+        )[0],
+        kycAccount,
+      })
+      .rpc({ skipPreflight: true, commitment: "confirmed" });
+
+    await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+    const event = await eventPromise;
+
+    // the callback refunds the staged input
+    expect(await provider.connection.getAccountInfo(kycMatchInput, "confirmed")).to.equal(null);
+    return event;
+  }
+
+  // [passed, reason] as decrypted by the user, the attester and the auditor
+  const decrypt = (event: any) => ({
+    user: cipher.decrypt(event.result, new Uint8Array(event.nonce)),
+    attester: attesterCipher.decrypt(event.attesterResult, new Uint8Array(event.attesterNonce)),
+    auditor: auditorCipher.decrypt(event.auditorResult, new Uint8Array(event.auditorNonce)),
+  });
+
+  it("passes a one-edit name difference and records it on zord", async () => {
+    const event = await runKycMatch(
+      { name: "ANANYA SHARMA", dob: 19990412 },
+      { name: "ANANYA SHARM", dob: 19990412 }
+    );
+
+    expect(event.user.toBase58()).to.equal(payer.toBase58());
+    expect(event.attester.toBase58()).to.equal(payer.toBase58());
+    expect(event.matched).to.equal(true);
+    const { user, attester, auditor } = decrypt(event);
+    expect(user).to.deep.equal([BigInt(1), BigInt(0)]);
+    expect(attester).to.deep.equal(user);
+    expect(auditor).to.deep.equal(user);
+
+    const kyc: any = await (zord.account as any).kycAccount.fetch(kycAccount);
+    expect(kyc.arciumMatch).to.equal(true);
+  });
+
+  it("fails a date of birth mismatch and records it on zord", async () => {
+    const event = await runKycMatch(
+      { name: "ANANYA SHARMA", dob: 19990412 },
+      { name: "ANANYA SHARMA", dob: 19990421 }
+    );
+
+    expect(event.matched).to.equal(false);
+    const { user, attester, auditor } = decrypt(event);
+    expect(user).to.deep.equal([BigInt(0), BigInt(REASON_DOB_MISMATCH)]);
+    expect(attester).to.deep.equal(user);
+    expect(auditor).to.deep.equal(user);
+
+    const kyc: any = await (zord.account as any).kycAccount.fetch(kycAccount);
+    expect(kyc.arciumMatch).to.equal(false);
+  });
+
+  it("fails a bad passport checksum even with an exact name match", async () => {
+    const event = await runKycMatch(
+      { name: "ANANYA SHARMA", dob: 19990412, checkDigit: "7" },
+      { name: "ANANYA SHARMA", dob: 19990412 }
+    );
+
+    expect(event.matched).to.equal(false);
+    const { user, attester, auditor } = decrypt(event);
+    expect(user).to.deep.equal([BigInt(0), BigInt(REASON_PASSPORT_CHECKSUM)]);
+    expect(attester).to.deep.equal(user);
+    expect(auditor).to.deep.equal(user);
+
+    const kyc: any = await (zord.account as any).kycAccount.fetch(kycAccount);
+    expect(kyc.arciumMatch).to.equal(false);
+  });
+
+  it("fails documents other than the attested ones", async () => {
+    const event = await runKycMatch(
+      { name: "ANANYA SHARMA", dob: 19990412 },
+      { name: "ANANYA SHARMA", dob: 19990412 },
+      [
+        { name: "RAHUL VERMA", dob: 19850101 },
+        { name: "RAHUL VERMA", dob: 19850101 },
+      ]
+    );
+
+    expect(event.matched).to.equal(false);
+    const { user, attester, auditor } = decrypt(event);
+    expect(user).to.deep.equal([BigInt(0), BigInt(REASON_NOT_ATTESTED)]);
+    expect(attester).to.deep.equal(user);
+    expect(auditor).to.deep.equal(user);
+
+    const kyc: any = await (zord.account as any).kycAccount.fetch(kycAccount);
+    expect(kyc.arciumMatch).to.equal(false);
+  });
+
+  it("encrypts to the rotated auditor key", async () => {
+    const auditor = newRecipient();
+    await program.methods
+      .rotateAuditor(Array.from(auditor.publicKey))
+      .accounts({ admin: payer })
+      .rpc({ commitment: "confirmed" });
+    auditorCipher = auditor.cipher;
+
+    const event = await runKycMatch(
+      { name: "ANANYA SHARMA", dob: 19990412 },
+      { name: "ANANYA SHARMA", dob: 19990412 }
+    );
+
+    expect(event.auditor).to.deep.equal(Array.from(auditor.publicKey));
+    expect(decrypt(event).auditor).to.deep.equal([BigInt(1), BigInt(0)]);
+  });
+
+  it("rejects an attester key from a wallet off zord's allow-list", async () => {
+    const intruder = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(intruder.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    try {
+      await program.methods
+        .registerAttesterKey(Array.from(newRecipient().publicKey))
+        .accounts({ attester: intruder.publicKey })
+        .signers([intruder])
+        .rpc({ commitment: "confirmed" });
+      expect.fail("an unlisted attester registered a key");
+    } catch (err: any) {
+      expect(err.error?.errorCode?.code).to.equal("UnknownAttester");
+    }
+  });
+
+  it("rejects an auditor rotation from anyone but the admin", async () => {
+    const intruder = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .rotateAuditor(Array.from(newRecipient().publicKey))
+        .accounts({ admin: intruder.publicKey })
+        .signers([intruder])
+        .rpc({ commitment: "confirmed" });
+      expect.fail("rotation by a non-admin succeeded");
+    } catch (err: any) {
+      expect(err.error?.errorCode?.code).to.equal("Unauthorized");
+    }
+  });
 });
@@ -1,5 +1,6 @@
 const { execSync } = require("child_process");
 const fs = require("fs");
+const crypto = require("crypto");
 const circomlib = require("circomlibjs");
 
 function run(cmd) {
@@ -12,10 +13,39 @@ function strToBigInt(str) {
   return BigInt("0x" + Buffer.from(str).toString("hex"));
 }
 
+// Decode a base58 Solana public key into its 32 raw bytes
+function base58Decode(str) {
+  const ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+  let n = 0n;
+  for (const c of str) {
+    const i = ALPHABET.indexOf(c);
+    if (i < 0) throw new Error("invalid base58 character: " + c);
+    n = n * 58n + BigInt(i);
+  }
+  return Buffer.from(n.toString(16).padStart(64, "0"), "hex");
+}
+
+// sha256("zord-user" || pubkey) with the top 3 bits cleared,
+// must match zord::circuit::user_key_commitment
+function userKeyCommitment(pubkey) {
+  const digest = crypto
+    .createHash("sha256")
+    .update(Buffer.concat([Buffer.from("zord-user"), base58Decode(pubkey)]))
+    .digest();
+  digest[0] &= 0x1f;
+  return BigInt("0x" + digest.toString("hex")).toString();
+}
+
 async function main() {
   // Load input
   const input = JSON.parse(fs.readFileSync("input.json"));
 
+  // The proof is only accepted by zord when submitted from this wallet
+  const userPubkey = process.env.USER_PUBKEY;
+  if (!userPubkey) {
+    throw new Error("set USER_PUBKEY to the wallet that will submit the proof");
+  }
+
   // Build Poseidon hasher
   const poseidon = await circomlib.buildPoseidon();
 
@@ -51,13 +81,18 @@ async function main() {
   input.pan_number = panNumberBigInt.toString();
   input.passport_hash = poseidon.F.toString(passportHash);
   input.pan_hash = poseidon.F.toString(panHash);
+  input.user_key_commitment = userKeyCommitment(userPubkey);
 
   // Save the updated input.json
   fs.writeFileSync("input.json", JSON.stringify(input, null, 2));
 
+  // Poseidon(passport_number, APP_SALT), the nullifier output of proof.circom
+  const nullifier = poseidon([passportNumberBigInt, strToBigInt("zord-nullifier-v1")]);
+
   console.log("Generated hashes:", {
     passport_hash: input.passport_hash,
-    pan_hash: input.pan_hash
+    pan_hash: input.pan_hash,
+    nullifier: poseidon.F.toString(nullifier)
   });
 
   const CIRCUIT_FILE = "proof.circom";
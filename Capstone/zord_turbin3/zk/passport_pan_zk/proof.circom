@@ -2,7 +2,9 @@ pragma circom 2.2.2;
 
 include "circomlib/circuits/poseidon.circom";
 
-template PassportPanMatch() {
+// APP_SALT scopes nullifiers to zord: the same passport yields unrelated
+// nullifiers in other apps, see zord::circuit::nullifier_seed
+template PassportPanMatch(APP_SALT) {
     signal input passport_name;
     signal input passport_dob;
     signal input passport_number;
@@ -14,6 +16,11 @@ template PassportPanMatch() {
     signal input passport_hash;
     signal input pan_hash;
 
+    // binds the proof to the submitting wallet, see zord::circuit::user_key_commitment
+    signal input user_key_commitment;
+    signal user_key_square;
+    user_key_square <== user_key_commitment * user_key_commitment;
+
     component passportHasher = Poseidon(3);
     passportHasher.inputs[0] <== passport_name;
     passportHasher.inputs[1] <== passport_dob;
@@ -33,6 +40,14 @@ template PassportPanMatch() {
 
     signal output valid;
     valid <== 1;
+
+    // one zord wallet per passport
+    signal output nullifier;
+    component nullifierHasher = Poseidon(2);
+    nullifierHasher.inputs[0] <== passport_number;
+    nullifierHasher.inputs[1] <== APP_SALT;
+    nullifier <== nullifierHasher.out;
 }
 
-component main = PassportPanMatch();
\ No newline at end of file
+// APP_SALT = "zord-nullifier-v1" as a big-endian integer
+component main {public [user_key_commitment]} = PassportPanMatch(41662587025568904160499281006144493811249);
\ No newline at end of file
@@ -0,0 +1,107 @@
+use anchor_lang::{error, AccountDeserialize, Result};
+use zord::errors::ZordError;
+use zord::state::kyc_account::{KycAccount, KycStatus};
+
+/// What a `KycAccount` means as of a given time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status {
+    Pending,
+    Verified { expires_at: i64 },
+    Expired { expires_at: i64 },
+    Suspended { reason: u8 },
+    Revoked { reason: u8 },
+}
+
+impl Status {
+    /// Mirrors `KycAccount::current_status`: a verification past `expires_at` is expired.
+    pub fn of(kyc: &KycAccount, now: i64) -> Self {
+        match kyc.current_status(now) {
+            KycStatus::Pending => Status::Pending,
+            KycStatus::Verified => Status::Verified {
+                expires_at: kyc.expires_at,
+            },
+            KycStatus::Expired => Status::Expired {
+                expires_at: kyc.expires_at,
+            },
+            KycStatus::Suspended => Status::Suspended {
+                reason: kyc.revocation_reason,
+            },
+            KycStatus::Revoked => Status::Revoked {
+                reason: kyc.revocation_reason,
+            },
+        }
+    }
+
+    pub fn is_verified(&self) -> bool {
+        matches!(self, Status::Verified { .. })
+    }
+}
+
+/// Decodes a `KycAccount` in either layout. v0 accounts (not yet run through
+/// `migrate_kyc`) come back as `migrate_kyc` would write them, with `version` 0.
+pub fn decode_kyc_account(data: &[u8]) -> Result<KycAccount> {
+    if data.len() == 8 + KycAccount::V0_SIZE {
+        return KycAccount::from_v0(data);
+    }
+    KycAccount::try_deserialize(&mut &data[..]).map_err(|_| error!(ZordError::InvalidKycAccount))
+}
+
+#[cfg(feature = "rpc")]
+pub use self::rpc::{fetch_kyc_account, FetchError};
+
+#[cfg(feature = "rpc")]
+mod rpc {
+    use std::fmt;
+
+    use anchor_lang::error;
+    use anchor_lang::prelude::Pubkey;
+    use solana_rpc_client::rpc_client::RpcClient;
+    use solana_rpc_client_api::client_error::Error as ClientError;
+    use zord::errors::ZordError;
+    use zord::state::kyc_account::KycAccount;
+
+    use crate::pda;
+
+    #[derive(Debug)]
+    pub enum FetchError {
+        Rpc(Box<ClientError>),
+        /// The `[b"kyc", user]` address holds something other than a zord `KycAccount`.
+        InvalidAccount(anchor_lang::error::Error),
+    }
+
+    impl fmt::Display for FetchError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                FetchError::Rpc(err) => write!(f, "rpc error: {err}"),
+                FetchError::InvalidAccount(err) => write!(f, "invalid kyc account: {err}"),
+            }
+        }
+    }
+
+    impl std::error::Error for FetchError {}
+
+    /// The user's `KycAccount`, or `None` before `initialize_kyc`.
+    pub fn fetch_kyc_account(
+        rpc: &RpcClient,
+        user: &Pubkey,
+    ) -> Result<Option<KycAccount>, FetchError> {
+        let (address, _) = pda::kyc(user);
+        let account = rpc
+            .get_account_with_commitment(&address, rpc.commitment())
+            .map_err(|err| FetchError::Rpc(Box::new(err)))?
+            .value;
+
+        let Some(account) = account else {
+            return Ok(None);
+        };
+        let invalid = || FetchError::InvalidAccount(error!(ZordError::InvalidKycAccount));
+        if account.owner != zord::ID {
+            return Err(invalid());
+        }
+        let kyc = super::decode_kyc_account(&account.data).map_err(FetchError::InvalidAccount)?;
+        if kyc.user != *user {
+            return Err(invalid());
+        }
+        Ok(Some(kyc))
+    }
+}
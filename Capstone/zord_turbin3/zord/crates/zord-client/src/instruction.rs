@@ -0,0 +1,88 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, InstructionData, Result, ToAccountMetas};
+use zord::attestation::Attestation;
+use zord::circuit;
+use zord::instructions::submit_attestation as submit;
+
+use crate::pda;
+
+pub fn initialize_kyc(user: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::InitializeKyc {
+            user: *user,
+            kyc_account: pda::kyc(user).0,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::InitializeKyc {}.data(),
+    }
+}
+
+/// The 64 bytes the attester signs for `attestation`: `user || sha256(borsh(attestation))`.
+pub fn attestation_message(user: &Pubkey, attestation: &Attestation) -> Result<[u8; 64]> {
+    Ok(submit::attestation_message(user, &attestation.hash()?))
+}
+
+/// The Ed25519 precompile instruction carrying the attester's signature, then
+/// `submit_attestation` itself; they must be sent in this order.
+pub fn submit_attestation(
+    user: &Pubkey,
+    attestation: Attestation,
+    attester: &Pubkey,
+    signature: &[u8; 64],
+) -> Result<[Instruction; 2]> {
+    let message = attestation_message(user, &attestation)?;
+    let precompile = solana_ed25519_program::new_ed25519_instruction_with_signature(
+        &message,
+        signature,
+        &attester.to_bytes(),
+    );
+
+    let submit = Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::SubmitAttestation {
+            user: *user,
+            kyc_account: pda::kyc(user).0,
+            attester_config: pda::attester_config().0,
+            instructions: sysvar::instructions::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::SubmitAttestation { attestation }.data(),
+    };
+    Ok([precompile, submit])
+}
+
+/// `verify_zk` for a proof and public inputs in the alt_bn128 layout, e.g. as
+/// written by zord-prover. The nullifier record is derived from the inputs.
+pub fn verify_zk(
+    user: &Pubkey,
+    vk_version: u32,
+    proof_bytes: Vec<u8>,
+    public_inputs_bytes: Vec<[u8; 32]>,
+) -> Instruction {
+    let (nullifier_record, _) = Pubkey::find_program_address(
+        &[b"nullifier", circuit::nullifier_seed(&public_inputs_bytes)],
+        &zord::ID,
+    );
+
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::VerifyZk {
+            verifier_config: pda::verifier_config().0,
+            kyc_account: pda::kyc(user).0,
+            nullifier_record,
+            user: *user,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::VerifyZk {
+            vk_version,
+            proof_bytes,
+            public_inputs_bytes,
+        }
+        .data(),
+    }
+}
@@ -0,0 +1,14 @@
+//! Client side of zord: PDA derivation, instruction builders and `KycAccount`
+//! decoding, so consumers stop rebuilding seeds and Anchor data by hand.
+//!
+//! Instruction data and account lists come from the program crate itself
+//! (`zord::instruction`, `zord::accounts`), so they cannot drift from the IDL.
+
+pub mod account;
+pub mod instruction;
+pub mod pda;
+
+pub use account::{decode_kyc_account, Status};
+#[cfg(feature = "rpc")]
+pub use account::{fetch_kyc_account, FetchError};
+pub use zord::ID;
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::Pubkey;
+
+/// `[b"kyc", user]`, the user's `KycAccount`.
+pub fn kyc(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"kyc", user.as_ref()], &zord::ID)
+}
+
+/// `[b"vk"]`, the verifier config holding the versioned verification keys.
+pub fn verifier_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vk"], &zord::ID)
+}
+
+/// `[b"attesters"]`, the attester allow-list.
+pub fn attester_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"attesters"], &zord::ID)
+}
+
+/// `[b"nullifier", nullifier]`, the wallet an identity document is bound to.
+pub fn nullifier(nullifier: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"nullifier", nullifier], &zord::ID)
+}
+
+/// `[b"claim", claim_id]`, the verifier for one selective-disclosure claim.
+pub fn claim_verifier(claim_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"claim", &[claim_id]], &zord::ID)
+}
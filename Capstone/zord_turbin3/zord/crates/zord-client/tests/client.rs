@@ -0,0 +1,267 @@
+//! Round-trips the builders' output through the program's own instruction
+//! types, and against `target/idl/zord.json` when `anchor build` has written it.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{AccountSerialize, AnchorDeserialize, Discriminator};
+use sha2::{Digest, Sha256};
+use zord::attestation::{Attestation, ATTESTATION_VERSION, DOC_ALL};
+use zord::circuit::{NR_PUBLIC_SIGNALS, NULLIFIER_INDEX};
+use zord::state::kyc_account::{
+    KycAccount, KycStatus, KYC_ACCOUNT_VERSION, KYC_RESERVED_LEN, REASON_FRAUD, REASON_NONE,
+    ZK_VERIFICATION_VALIDITY,
+};
+use zord_client::{decode_kyc_account, instruction, pda, Status};
+
+const NOW: i64 = 1_700_000_000;
+
+fn sighash(name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("global:{name}"));
+    hash[..8].try_into().unwrap()
+}
+
+fn args<T: AnchorDeserialize>(ix: &Instruction, name: &str) -> T {
+    assert_eq!(ix.program_id, zord::ID);
+    assert_eq!(ix.data[..8], sighash(name), "{name} discriminator");
+    T::try_from_slice(&ix.data[8..]).unwrap()
+}
+
+fn attestation(user: &Pubkey, issuer: &Pubkey) -> Attestation {
+    Attestation {
+        version: ATTESTATION_VERSION,
+        subject: *user,
+        issuer: *issuer,
+        issued_at: NOW - 60,
+        expires_at: NOW + 365 * 24 * 60 * 60,
+        document_flags: DOC_ALL,
+        claims_commitment: [42u8; 32],
+        document_commitment: [9u8; 32],
+    }
+}
+
+fn kyc_account(user: &Pubkey, status: KycStatus) -> KycAccount {
+    KycAccount {
+        user: *user,
+        status,
+        attestation_hash: [7u8; 32],
+        timestamp: NOW,
+        expires_at: NOW + 100,
+        verified_vk_version: 1,
+        attester: Pubkey::new_unique(),
+        revocation_reason: REASON_NONE,
+        version: KYC_ACCOUNT_VERSION,
+        claims: 0b101,
+        arcium_match: false,
+        document_commitment: [0u8; 32],
+        reserved: [0u8; KYC_RESERVED_LEN],
+    }
+}
+
+fn encode(kyc: &KycAccount) -> Vec<u8> {
+    let mut data = Vec::new();
+    kyc.try_serialize(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn pdas_match_the_program_seeds() {
+    let user = Pubkey::new_unique();
+    let nullifier = [9u8; 32];
+
+    let derive = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &zord::ID);
+    assert_eq!(pda::kyc(&user), derive(&[b"kyc", user.as_ref()]));
+    assert_eq!(pda::verifier_config(), derive(&[b"vk"]));
+    assert_eq!(pda::attester_config(), derive(&[b"attesters"]));
+    assert_eq!(
+        pda::nullifier(&nullifier),
+        derive(&[b"nullifier", &nullifier])
+    );
+    assert_eq!(pda::claim_verifier(3), derive(&[b"claim", &[3]]));
+}
+
+#[test]
+fn initialize_kyc_round_trips() {
+    let user = Pubkey::new_unique();
+    let ix = instruction::initialize_kyc(&user);
+
+    let _: zord::instruction::InitializeKyc = args(&ix, "initialize_kyc");
+    assert_eq!(ix.data.len(), 8);
+    assert_eq!(ix.accounts[0].pubkey, user);
+    assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+    assert_eq!(ix.accounts[1].pubkey, pda::kyc(&user).0);
+}
+
+#[test]
+fn submit_attestation_round_trips() {
+    let user = Pubkey::new_unique();
+    let attester = Pubkey::new_unique();
+    let attestation = attestation(&user, &attester);
+    let signature = [5u8; 64];
+
+    let [precompile, submit] =
+        instruction::submit_attestation(&user, attestation.clone(), &attester, &signature).unwrap();
+
+    let decoded: zord::instruction::SubmitAttestation = args(&submit, "submit_attestation");
+    assert_eq!(decoded.attestation, attestation);
+    assert_eq!(submit.accounts[1].pubkey, pda::kyc(&user).0);
+    assert_eq!(submit.accounts[2].pubkey, pda::attester_config().0);
+
+    // the precompile carries the attester key, the signature and `user || hash`
+    let message = instruction::attestation_message(&user, &attestation).unwrap();
+    assert_eq!(message[..32], user.to_bytes());
+    assert_eq!(message[32..], attestation.hash().unwrap());
+    assert_eq!(precompile.program_id, solana_sdk_ids::ed25519_program::ID);
+    let contains = |needle: &[u8]| precompile.data.windows(needle.len()).any(|w| w == needle);
+    assert!(contains(&attester.to_bytes()));
+    assert!(contains(&signature));
+    assert!(contains(&message));
+}
+
+#[test]
+fn verify_zk_round_trips() {
+    let user = Pubkey::new_unique();
+    let mut public_inputs = vec![[0u8; 32]; NR_PUBLIC_SIGNALS];
+    public_inputs[NULLIFIER_INDEX] = [3u8; 32];
+    let proof = vec![1u8; 256];
+
+    let ix = instruction::verify_zk(&user, 2, proof.clone(), public_inputs.clone());
+
+    let decoded: zord::instruction::VerifyZk = args(&ix, "verify_zk");
+    assert_eq!(decoded.vk_version, 2);
+    assert_eq!(decoded.proof_bytes, proof);
+    assert_eq!(decoded.public_inputs_bytes, public_inputs);
+
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(keys[0], pda::verifier_config().0);
+    assert_eq!(keys[1], pda::kyc(&user).0);
+    assert_eq!(keys[2], pda::nullifier(&[3u8; 32]).0);
+    assert_eq!(keys[3], user);
+}
+
+#[test]
+fn decodes_current_and_v0_accounts() {
+    let user = Pubkey::new_unique();
+    let kyc = kyc_account(&user, KycStatus::Verified);
+    let data = encode(&kyc);
+
+    let decoded = decode_kyc_account(&data).unwrap();
+    assert_eq!(decoded.user, user);
+    assert_eq!(decoded.claims, 0b101);
+    assert_eq!(decoded.version, KYC_ACCOUNT_VERSION);
+
+    // a v0 account holds user, is_verified, attestation_hash and timestamp
+    let mut v0 = KycAccount::DISCRIMINATOR.to_vec();
+    v0.extend_from_slice(user.as_ref());
+    v0.push(1);
+    v0.extend_from_slice(&[7u8; 32]);
+    v0.extend_from_slice(&NOW.to_le_bytes());
+    let decoded = decode_kyc_account(&v0).unwrap();
+    assert_eq!(decoded.user, user);
+    assert_eq!(decoded.status, KycStatus::Verified);
+    assert_eq!(decoded.attestation_hash, [7u8; 32]);
+    assert_eq!(decoded.timestamp, NOW);
+    assert_eq!(decoded.expires_at, NOW + ZK_VERIFICATION_VALIDITY);
+    assert_eq!(decoded.version, 0);
+    assert_eq!(decoded.claims, 0);
+
+    v0[8 + 32] = 0;
+    assert_eq!(decode_kyc_account(&v0).unwrap().status, KycStatus::Pending);
+    v0[8 + 32] = 2;
+    assert!(decode_kyc_account(&v0).is_err());
+}
+
+#[test]
+fn rejects_foreign_data() {
+    let user = Pubkey::new_unique();
+    let mut data = encode(&kyc_account(&user, KycStatus::Pending));
+    data[..8].copy_from_slice(&[0u8; 8]);
+    assert!(decode_kyc_account(&data).is_err());
+
+    assert!(decode_kyc_account(KycAccount::DISCRIMINATOR).is_err());
+    assert!(decode_kyc_account(&[]).is_err());
+}
+
+#[test]
+fn status_follows_the_account() {
+    let user = Pubkey::new_unique();
+    let mut kyc = kyc_account(&user, KycStatus::Verified);
+    let expires_at = kyc.expires_at;
+
+    assert_eq!(Status::of(&kyc, NOW), Status::Verified { expires_at });
+    assert!(Status::of(&kyc, NOW).is_verified());
+    assert_eq!(Status::of(&kyc, expires_at), Status::Expired { expires_at });
+    assert!(!Status::of(&kyc, expires_at).is_verified());
+
+    kyc.status = KycStatus::Suspended;
+    kyc.revocation_reason = REASON_FRAUD;
+    assert_eq!(
+        Status::of(&kyc, NOW),
+        Status::Suspended {
+            reason: REASON_FRAUD
+        }
+    );
+
+    kyc.status = KycStatus::Revoked;
+    assert_eq!(
+        Status::of(&kyc, NOW),
+        Status::Revoked {
+            reason: REASON_FRAUD
+        }
+    );
+
+    kyc.status = KycStatus::Pending;
+    assert_eq!(Status::of(&kyc, NOW), Status::Pending);
+}
+
+#[test]
+#[ignore = "needs target/idl/zord.json from `anchor build`"]
+fn builders_match_the_idl() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/idl/zord.json");
+    let idl = std::fs::read_to_string(path)
+        .unwrap_or_else(|_| panic!("{path} not found, run `anchor build` first"));
+    let idl: serde_json::Value = serde_json::from_str(&idl).unwrap();
+
+    let user = Pubkey::new_unique();
+    let attester = Pubkey::new_unique();
+    let [_, submit] = instruction::submit_attestation(
+        &user,
+        attestation(&user, &attester),
+        &attester,
+        &[0u8; 64],
+    )
+    .unwrap();
+    let built = [
+        ("initialize_kyc", instruction::initialize_kyc(&user)),
+        ("submit_attestation", submit),
+        (
+            "verify_zk",
+            instruction::verify_zk(&user, 1, vec![], vec![[0u8; 32]; NR_PUBLIC_SIGNALS]),
+        ),
+    ];
+
+    for (name, ix) in built {
+        let entry = idl["instructions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry["name"] == name)
+            .unwrap_or_else(|| panic!("{name} missing from the IDL"));
+
+        let discriminator: Vec<u8> =
+            serde_json::from_value(entry["discriminator"].clone()).unwrap();
+        assert_eq!(discriminator, ix.data[..8], "{name} discriminator");
+
+        let accounts = entry["accounts"].as_array().unwrap();
+        assert_eq!(accounts.len(), ix.accounts.len(), "{name} account count");
+        for (account, meta) in accounts.iter().zip(&ix.accounts) {
+            let flag = |key: &str| account[key].as_bool().unwrap_or(false);
+            assert_eq!(
+                flag("writable"),
+                meta.is_writable,
+                "{name}.{}",
+                account["name"]
+            );
+            assert_eq!(flag("signer"), meta.is_signer, "{name}.{}", account["name"]);
+        }
+    }
+}
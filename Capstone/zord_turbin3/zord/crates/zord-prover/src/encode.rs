@@ -0,0 +1,53 @@
+// arkworks types -> the big-endian alt_bn128 layout `verify_zk` reads,
+// the same layout tests/zord.ts builds from snarkjs' proof.json.
+
+use ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{Proof, VerifyingKey};
+use zord::groth16::{FIELD_LEN, G1_LEN, G2_LEN};
+use zord::state::verifier_config::VerificationKeyInput;
+
+/// A || B || C, `groth16::PROOF_LEN` bytes.
+pub fn proof(proof: &Proof<Bn254>) -> Vec<u8> {
+    [&g1(&proof.a)[..], &g2(&proof.b)[..], &g1(&proof.c)[..]].concat()
+}
+
+/// The key as `register_vk` / `rotate_vk` take it.
+pub fn verifying_key(vk: &VerifyingKey<Bn254>) -> VerificationKeyInput {
+    VerificationKeyInput {
+        vk_alpha_g1: g1(&vk.alpha_g1),
+        vk_beta_g2: g2(&vk.beta_g2),
+        vk_gamma_g2: g2(&vk.gamma_g2),
+        vk_delta_g2: g2(&vk.delta_g2),
+        vk_ic: vk.gamma_abc_g1.iter().map(g1).collect(),
+    }
+}
+
+pub fn scalar(value: &Fr) -> [u8; FIELD_LEN] {
+    let mut bytes = [0u8; FIELD_LEN];
+    bytes.copy_from_slice(&value.into_bigint().to_bytes_be());
+    bytes
+}
+
+fn fq(value: &Fq) -> [u8; FIELD_LEN] {
+    let mut bytes = [0u8; FIELD_LEN];
+    bytes.copy_from_slice(&value.into_bigint().to_bytes_be());
+    bytes
+}
+
+fn g1(point: &G1Affine) -> [u8; G1_LEN] {
+    let mut bytes = [0u8; G1_LEN];
+    bytes[..32].copy_from_slice(&fq(&point.x));
+    bytes[32..].copy_from_slice(&fq(&point.y));
+    bytes
+}
+
+// EIP-197: x_c1 || x_c0 || y_c1 || y_c0
+fn g2(point: &G2Affine) -> [u8; G2_LEN] {
+    let mut bytes = [0u8; G2_LEN];
+    bytes[..32].copy_from_slice(&fq(&point.x.c1));
+    bytes[32..64].copy_from_slice(&fq(&point.x.c0));
+    bytes[64..96].copy_from_slice(&fq(&point.y.c1));
+    bytes[96..].copy_from_slice(&fq(&point.y.c0));
+    bytes
+}
@@ -0,0 +1,100 @@
+// input.json -> circom input signals, matching what generate_proof.js feeds
+// the witness generator.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::Pubkey;
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use light_poseidon::{Poseidon, PoseidonHasher};
+use num_bigint::{BigInt, BigUint};
+use serde_json::Value;
+use zord::circuit;
+
+use crate::Result;
+
+pub const PASSPORT_FIELDS: [&str; 3] = ["passport_name", "passport_dob", "passport_number"];
+pub const PAN_FIELDS: [&str; 3] = ["pan_name", "pan_dob", "pan_number"];
+
+/// How the document fields in input.json are written.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Encoding {
+    /// Field elements as decimal strings or numbers, hashes included, as
+    /// generate_proof.js writes input.json back.
+    Field,
+    /// The document strings themselves. They are packed like `strToBigInt`
+    /// and `passport_hash` / `pan_hash` are computed here.
+    Raw,
+}
+
+/// Named input signals for `PassportPanMatch`, every value reduced mod r.
+///
+/// `user_key_commitment` always comes from `user`, whatever the file says.
+pub fn circuit_inputs(
+    input: &Value,
+    encoding: Encoding,
+    user: &Pubkey,
+) -> Result<BTreeMap<String, Fr>> {
+    let object = input
+        .as_object()
+        .ok_or("input.json must be a JSON object")?;
+
+    let mut signals = BTreeMap::new();
+    match encoding {
+        Encoding::Field => {
+            for (name, value) in object {
+                signals.insert(name.clone(), field_value(name, value)?);
+            }
+        }
+        Encoding::Raw => {
+            for fields in [PASSPORT_FIELDS, PAN_FIELDS] {
+                for name in fields {
+                    let value = object
+                        .get(name)
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| format!("{name} must be a string"))?;
+                    signals.insert(name.to_string(), str_to_field(value));
+                }
+            }
+            let passport_hash = poseidon(&PASSPORT_FIELDS.map(|name| signals[name]))?;
+            let pan_hash = poseidon(&PAN_FIELDS.map(|name| signals[name]))?;
+            signals.insert("passport_hash".to_string(), passport_hash);
+            signals.insert("pan_hash".to_string(), pan_hash);
+        }
+    }
+
+    let commitment = circuit::user_key_commitment(user);
+    signals.insert(
+        "user_key_commitment".to_string(),
+        Fr::from_be_bytes_mod_order(&commitment),
+    );
+    Ok(signals)
+}
+
+/// The string's UTF-8 bytes as a big-endian integer, mod r (`strToBigInt` in
+/// generate_proof.js, reduced the way circom reduces its inputs).
+pub fn str_to_field(value: &str) -> Fr {
+    Fr::from_be_bytes_mod_order(value.as_bytes())
+}
+
+/// circomlib Poseidon, as used by proof.circom.
+pub fn poseidon(inputs: &[Fr]) -> Result<Fr> {
+    let mut hasher = Poseidon::<Fr>::new_circom(inputs.len())?;
+    Ok(hasher.hash(inputs)?)
+}
+
+pub fn to_bigint(value: &Fr) -> BigInt {
+    BigInt::from_bytes_be(num_bigint::Sign::Plus, &value.into_bigint().to_bytes_be())
+}
+
+fn field_value(name: &str, value: &Value) -> Result<Fr> {
+    let decimal = match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) if n.is_u64() => n.to_string(),
+        _ => return Err(format!("{name} must be a decimal string or unsigned integer").into()),
+    };
+    let value = decimal
+        .parse::<BigUint>()
+        .map_err(|_| format!("{name} is not a decimal number"))?;
+    Ok(Fr::from_be_bytes_mod_order(&value.to_bytes_be()))
+}
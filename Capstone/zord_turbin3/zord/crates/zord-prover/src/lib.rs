@@ -0,0 +1,170 @@
+//! Native proving for the passport/PAN circuit in zk/passport_pan_zk.
+//!
+//! Loads the circom witness generator, `proof.r1cs` and `circuit_final.zkey`,
+//! and produces proofs already in the byte layout `verify_zk` takes. Local
+//! verification runs zord's own verifier, so a proof that passes here passes
+//! on chain for the same key and wallet.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use anchor_lang::prelude::Pubkey;
+use ark_bn254::{Bn254, Fr};
+use ark_circom::{read_zkey, CircomBuilder, CircomConfig, CircomReduction};
+use ark_groth16::{Groth16, ProvingKey};
+use rand::{CryptoRng, RngCore};
+use serde_json::{json, Value};
+use zord::circuit;
+use zord::groth16::{self, Groth16Proof, FIELD_LEN};
+use zord::state::verifier_config::{VerificationKeyEntry, VerificationKeyInput};
+
+pub mod encode;
+pub mod input;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Build outputs of generate_proof.js, relative to the circuit directory.
+pub struct Artifacts {
+    pub wasm: PathBuf,
+    pub r1cs: PathBuf,
+    pub zkey: PathBuf,
+}
+
+impl Artifacts {
+    pub fn in_dir(dir: &Path) -> Self {
+        Self {
+            wasm: dir.join("proof_js/proof.wasm"),
+            r1cs: dir.join("proof.r1cs"),
+            zkey: dir.join("circuit_final.zkey"),
+        }
+    }
+}
+
+/// A proof and its public signals, ready for `verify_zk`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZkProof {
+    pub user: Pubkey,
+    pub proof_bytes: Vec<u8>,
+    pub public_inputs: Vec<[u8; FIELD_LEN]>,
+}
+
+impl ZkProof {
+    pub fn nullifier(&self) -> Option<&[u8; FIELD_LEN]> {
+        self.public_inputs.get(circuit::NULLIFIER_INDEX)
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "user": self.user.to_string(),
+            "proof": hex::encode(&self.proof_bytes),
+            "public_inputs": self.public_inputs.iter().map(hex::encode).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let user = value["user"].as_str().ok_or("user missing")?.parse()?;
+        let proof_bytes = hex::decode(value["proof"].as_str().ok_or("proof missing")?)?;
+        let public_inputs = value["public_inputs"]
+            .as_array()
+            .ok_or("public_inputs missing")?
+            .iter()
+            .map(|input| {
+                let mut bytes = [0u8; FIELD_LEN];
+                hex::decode_to_slice(
+                    input.as_str().ok_or("public input is not a string")?,
+                    &mut bytes,
+                )?;
+                Ok(bytes)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            user,
+            proof_bytes,
+            public_inputs,
+        })
+    }
+}
+
+pub struct Prover {
+    artifacts: Artifacts,
+    pk: ProvingKey<Bn254>,
+    // wasmer's WASI layer needs a Tokio reactor to load the witness generator
+    runtime: tokio::runtime::Runtime,
+}
+
+impl Prover {
+    /// Fails when the zkey was set up for a different number of public
+    /// signals than `zord::circuit` reads, i.e. a build of an older circuit.
+    pub fn load(artifacts: Artifacts) -> Result<Self> {
+        let mut zkey = BufReader::new(File::open(&artifacts.zkey)?);
+        let (pk, _) = read_zkey(&mut zkey)?;
+        let n_public = pk.vk.gamma_abc_g1.len() - 1;
+        if n_public != circuit::NR_PUBLIC_SIGNALS {
+            return Err(format!(
+                "{} has {} public signals, zord expects {}; rebuild the circuit with generate_proof.js",
+                artifacts.zkey.display(),
+                n_public,
+                circuit::NR_PUBLIC_SIGNALS
+            )
+            .into());
+        }
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            artifacts,
+            pk,
+            runtime,
+        })
+    }
+
+    /// The zkey's verification key, as `register_vk` takes it.
+    pub fn verification_key(&self) -> VerificationKeyInput {
+        encode::verifying_key(&self.pk.vk)
+    }
+
+    /// Computes the witness for `signals` and proves it for `user`.
+    pub fn prove<R: RngCore + CryptoRng>(
+        &self,
+        signals: &BTreeMap<String, Fr>,
+        user: &Pubkey,
+        rng: &mut R,
+    ) -> Result<ZkProof> {
+        let _runtime = self.runtime.enter();
+        let mut cfg = CircomConfig::<Fr>::new(&self.artifacts.wasm, &self.artifacts.r1cs)
+            .map_err(|e| e.to_string())?;
+        cfg.sanity_check = true;
+
+        let mut builder = CircomBuilder::new(cfg);
+        for (name, value) in signals {
+            builder.push_input(name, input::to_bigint(value));
+        }
+        let circom = builder.build().map_err(|e| e.to_string())?;
+        let public_inputs = circom
+            .get_public_inputs()
+            .ok_or("witness has no public signals")?;
+
+        let proof = Groth16::<Bn254, CircomReduction>::create_random_proof_with_reduction(
+            circom, &self.pk, rng,
+        )?;
+        Ok(ZkProof {
+            user: *user,
+            proof_bytes: encode::proof(&proof),
+            public_inputs: public_inputs.iter().map(encode::scalar).collect(),
+        })
+    }
+}
+
+/// Everything `verify_zk` checks about the proof itself: the public signals
+/// are a passing match for `proof.user`, and the pairing check holds for `vk`.
+pub fn verify(proof: &ZkProof, vk: VerificationKeyInput) -> Result<()> {
+    circuit::check_public_inputs(&proof.public_inputs, &proof.user)?;
+
+    let entry = VerificationKeyEntry::new(0, vk)?;
+    let vk = entry.verifying_key()?;
+    let groth16_proof = Groth16Proof::try_from_bytes(&proof.proof_bytes)?;
+    groth16::verify(&groth16_proof, &proof.public_inputs, &vk)?;
+    Ok(())
+}
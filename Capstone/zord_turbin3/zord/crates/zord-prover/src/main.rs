@@ -0,0 +1,113 @@
+// zord-prover: native replacement for generate_proof.js.
+//
+//   zord-prover prove --user <PUBKEY> [--zk-dir DIR] [--input FILE] [--raw] [--out FILE]
+//   zord-prover verify --proof FILE [--zk-dir DIR]
+//
+// `prove` writes {user, proof, public_inputs} as hex, already in the layout
+// `verify_zk` takes, and verifies it before writing. `verify` re-checks such
+// a file against the circuit's zkey.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anchor_lang::prelude::Pubkey;
+use rand::rngs::OsRng;
+use zord_prover::input::{self, Encoding};
+use zord_prover::{Artifacts, Prover, Result, ZkProof};
+
+const DEFAULT_ZK_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../zk/passport_pan_zk");
+
+const USAGE: &str = "usage:
+  zord-prover prove --user <PUBKEY> [--zk-dir DIR] [--input FILE] [--raw] [--out FILE]
+  zord-prover verify --proof FILE [--zk-dir DIR]";
+
+struct Args {
+    command: String,
+    zk_dir: PathBuf,
+    user: Option<Pubkey>,
+    input: Option<PathBuf>,
+    proof: Option<PathBuf>,
+    out: Option<PathBuf>,
+    encoding: Encoding,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = std::env::args().skip(1);
+    let command = args.next().ok_or(USAGE)?;
+    let mut parsed = Args {
+        command,
+        zk_dir: PathBuf::from(DEFAULT_ZK_DIR),
+        user: None,
+        input: None,
+        proof: None,
+        out: None,
+        encoding: Encoding::Field,
+    };
+
+    while let Some(flag) = args.next() {
+        if flag == "--raw" {
+            parsed.encoding = Encoding::Raw;
+            continue;
+        }
+        let value = args.next().ok_or_else(|| format!("{flag} needs a value"))?;
+        match flag.as_str() {
+            "--zk-dir" => parsed.zk_dir = value.into(),
+            "--user" => parsed.user = Some(value.parse()?),
+            "--input" => parsed.input = Some(value.into()),
+            "--proof" => parsed.proof = Some(value.into()),
+            "--out" => parsed.out = Some(value.into()),
+            _ => return Err(format!("unknown flag {flag}\n{USAGE}").into()),
+        }
+    }
+    Ok(parsed)
+}
+
+fn prove(args: &Args, prover: &Prover) -> Result<()> {
+    let user = args.user.ok_or("--user is required")?;
+    let input_path = args
+        .input
+        .clone()
+        .unwrap_or_else(|| args.zk_dir.join("input.json"));
+    let input = serde_json::from_str(&fs::read_to_string(&input_path)?)?;
+
+    let signals = input::circuit_inputs(&input, args.encoding, &user)?;
+    let proof = prover.prove(&signals, &user, &mut OsRng)?;
+    zord_prover::verify(&proof, prover.verification_key())?;
+
+    let json = serde_json::to_string_pretty(&proof.to_json())?;
+    match &args.out {
+        Some(out) => fs::write(out, json)?,
+        None => println!("{json}"),
+    }
+    eprintln!("proof verified for {user}");
+    Ok(())
+}
+
+fn verify(args: &Args, prover: &Prover) -> Result<()> {
+    let path = args.proof.as_ref().ok_or("--proof is required")?;
+    let proof = ZkProof::from_json(&serde_json::from_str(&fs::read_to_string(path)?)?)?;
+    zord_prover::verify(&proof, prover.verification_key())?;
+    eprintln!("proof verified for {}", proof.user);
+    Ok(())
+}
+
+fn run() -> Result<()> {
+    let args = parse_args()?;
+    let prover = Prover::load(Artifacts::in_dir(&args.zk_dir))?;
+    match args.command.as_str() {
+        "prove" => prove(&args, &prover),
+        "verify" => verify(&args, &prover),
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,175 @@
+// Native tests for zord-prover. The circom round trip runs the compiled
+// circuit in zk/passport_pan_zk, so it fails until those artifacts are rebuilt
+// for every change to proof.circom; rerun generate_proof.js to refresh them.
+
+use std::path::Path;
+
+use anchor_lang::prelude::Pubkey;
+use ark_bn254::{Bn254, Fr};
+use ark_ff::PrimeField;
+use ark_groth16::Groth16;
+use ark_relations::lc;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use rand::rngs::{OsRng, StdRng};
+use rand::SeedableRng;
+use serde_json::json;
+use zord::circuit;
+use zord_prover::input::{self, Encoding};
+use zord_prover::{encode, Artifacts, Prover, ZkProof};
+
+const ZK_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../zk/passport_pan_zk");
+
+// Public signals tied to witnesses by `x * x == x_sq`, standing in for the
+// circom circuit when checking the byte layout against zord's verifier.
+struct SignalsCircuit {
+    inputs: Vec<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for SignalsCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        for value in self.inputs {
+            let input = cs.new_input_variable(|| Ok(value))?;
+            let square = cs.new_witness_variable(|| Ok(value * value))?;
+            cs.enforce_constraint(lc!() + input, lc!() + input, lc!() + square)?;
+        }
+        Ok(())
+    }
+}
+
+fn decimal(value: &Fr) -> String {
+    value.into_bigint().to_string()
+}
+
+fn documents() -> serde_json::Value {
+    json!({
+        "passport_name": "JOHN DOE",
+        "passport_dob": "1990-01-01",
+        "passport_number": "P1234567",
+        "pan_name": "JOHN DOE",
+        "pan_dob": "1990-01-01",
+        "pan_number": "ABCDE1234F",
+    })
+}
+
+#[test]
+fn poseidon_matches_circomlib() {
+    // circomlibjs poseidon([1, 2])
+    let hash = input::poseidon(&[Fr::from(1u64), Fr::from(2u64)]).unwrap();
+    assert_eq!(
+        decimal(&hash),
+        "7853200120776062878684798364095072458815029376092732009249414926327459813530"
+    );
+}
+
+#[test]
+fn raw_inputs_are_encoded_like_generate_proof_js() {
+    let user = Pubkey::new_unique();
+    let signals = input::circuit_inputs(&documents(), Encoding::Raw, &user).unwrap();
+
+    // strToBigInt("JOHN DOE")
+    assert_eq!(signals["passport_name"], Fr::from(0x4a4f484e20444f45u64));
+    let passport = ["JOHN DOE", "1990-01-01", "P1234567"].map(input::str_to_field);
+    assert_eq!(
+        signals["passport_hash"],
+        input::poseidon(&passport).unwrap()
+    );
+    assert_eq!(
+        encode::scalar(&signals["user_key_commitment"]),
+        circuit::user_key_commitment(&user)
+    );
+    assert_eq!(signals.len(), 9);
+}
+
+#[test]
+fn field_inputs_are_reduced_and_bound_to_the_user() {
+    let user = Pubkey::new_unique();
+    let modulus = Fr::MODULUS.to_string();
+    let above =
+        (num_bigint::BigUint::parse_bytes(modulus.as_bytes(), 10).unwrap() + 5u32).to_string();
+    let input = json!({
+        "passport_name": above,
+        "passport_dob": 7,
+        "user_key_commitment": "1",
+    });
+
+    let signals = input::circuit_inputs(&input, Encoding::Field, &user).unwrap();
+    assert_eq!(signals["passport_name"], Fr::from(5u64));
+    assert_eq!(signals["passport_dob"], Fr::from(7u64));
+    assert_eq!(
+        encode::scalar(&signals["user_key_commitment"]),
+        circuit::user_key_commitment(&user)
+    );
+
+    let bad = json!({ "passport_name": "JOHN DOE" });
+    assert!(input::circuit_inputs(&bad, Encoding::Field, &user).is_err());
+}
+
+#[test]
+fn encoded_proofs_pass_zords_verifier() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let user = Pubkey::new_unique();
+    let inputs = vec![
+        Fr::from(1u64),
+        Fr::from(42u64),
+        Fr::from_be_bytes_mod_order(&circuit::user_key_commitment(&user)),
+    ];
+
+    let blank = SignalsCircuit {
+        inputs: vec![Fr::from(0u64); inputs.len()],
+    };
+    let pk = Groth16::<Bn254>::generate_random_parameters_with_reduction(blank, &mut rng).unwrap();
+    let proof = Groth16::<Bn254>::create_random_proof_with_reduction(
+        SignalsCircuit {
+            inputs: inputs.clone(),
+        },
+        &pk,
+        &mut rng,
+    )
+    .unwrap();
+
+    let zk_proof = ZkProof {
+        user,
+        proof_bytes: encode::proof(&proof),
+        public_inputs: inputs.iter().map(encode::scalar).collect(),
+    };
+    let vk = encode::verifying_key(&pk.vk);
+    zord_prover::verify(&zk_proof, vk.clone()).unwrap();
+    assert_eq!(
+        zk_proof.nullifier(),
+        Some(&encode::scalar(&Fr::from(42u64)))
+    );
+
+    let round_trip = ZkProof::from_json(&zk_proof.to_json()).unwrap();
+    assert_eq!(round_trip, zk_proof);
+
+    // bound to `user`, so the same proof fails for any other wallet
+    let foreign = ZkProof {
+        user: Pubkey::new_unique(),
+        ..zk_proof.clone()
+    };
+    assert!(zord_prover::verify(&foreign, vk.clone()).is_err());
+
+    let mut tampered = zk_proof;
+    tampered.public_inputs[circuit::NULLIFIER_INDEX] = encode::scalar(&Fr::from(43u64));
+    assert!(zord_prover::verify(&tampered, vk).is_err());
+}
+
+#[test]
+fn proves_the_passport_pan_circuit() {
+    let artifacts = Artifacts::in_dir(Path::new(ZK_DIR));
+    let prover = Prover::load(artifacts).unwrap();
+
+    let user = Pubkey::new_unique();
+    let signals = input::circuit_inputs(&documents(), Encoding::Raw, &user).unwrap();
+    let proof = prover.prove(&signals, &user, &mut OsRng).unwrap();
+    zord_prover::verify(&proof, prover.verification_key()).unwrap();
+
+    let passport_number = input::str_to_field("P1234567");
+    let salt = input::str_to_field("zord-nullifier-v1");
+    assert_eq!(
+        proof.nullifier(),
+        Some(&encode::scalar(
+            &input::poseidon(&[passport_number, salt]).unwrap()
+        ))
+    );
+}
@@ -0,0 +1,84 @@
+// Generates `$OUT_DIR/vk.rs` from the circuit's snarkjs verification key so the
+// on-chain key can never drift from zk/passport_pan_zk/verification_key.json.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const VK_JSON: &str = "../../../zk/passport_pan_zk/verification_key.json";
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let vk_path = manifest_dir.join(VK_JSON);
+    println!("cargo:rerun-if-changed={}", vk_path.display());
+
+    let raw = fs::read_to_string(&vk_path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", vk_path.display(), e));
+    let vk: serde_json::Value = serde_json::from_str(&raw).expect("verification key is not valid JSON");
+
+    assert_eq!(vk["protocol"], "groth16", "only groth16 keys are supported");
+    assert_eq!(vk["curve"], "bn128", "only bn128 keys are supported");
+
+    let n_public = vk["nPublic"].as_u64().expect("nPublic missing") as usize;
+    let ic = vk["IC"].as_array().expect("IC missing");
+    assert_eq!(ic.len(), n_public + 1, "IC must hold nPublic + 1 points");
+
+    let mut out = String::new();
+    out.push_str("// @generated by build.rs from zk/passport_pan_zk/verification_key.json\n\n");
+    out.push_str(&format!("pub const NR_PUBINPUTS: usize = {};\n\n", n_public));
+    out.push_str(&byte_const("VK_ALPHA_G1", &g1(&vk["vk_alpha_1"])));
+    out.push_str(&byte_const("VK_BETA_G2", &g2(&vk["vk_beta_2"])));
+    out.push_str(&byte_const("VK_GAMMA_G2", &g2(&vk["vk_gamma_2"])));
+    out.push_str(&byte_const("VK_DELTA_G2", &g2(&vk["vk_delta_2"])));
+
+    out.push_str(&format!("pub const VK_IC: [[u8; 64]; {}] = [\n", ic.len()));
+    for point in ic {
+        out.push_str(&format!("    {},\n", byte_array(&g1(point))));
+    }
+    out.push_str("];\n");
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("vk.rs");
+    fs::write(&out_path, out).expect("failed to write generated vk.rs");
+}
+
+// G1 (affine, projective z dropped): x || y
+fn g1(point: &serde_json::Value) -> Vec<u8> {
+    [field(&point[0]), field(&point[1])].concat()
+}
+
+// G2 in EIP-197 order: x_c1 || x_c0 || y_c1 || y_c0
+fn g2(point: &serde_json::Value) -> Vec<u8> {
+    [
+        field(&point[0][1]),
+        field(&point[0][0]),
+        field(&point[1][1]),
+        field(&point[1][0]),
+    ]
+    .concat()
+}
+
+// decimal string -> 32 byte big-endian
+fn field(value: &serde_json::Value) -> [u8; 32] {
+    let digits = value.as_str().expect("field element must be a decimal string");
+    let mut bytes = [0u8; 32];
+    for digit in digits.bytes() {
+        assert!(digit.is_ascii_digit(), "invalid field element {}", digits);
+        let mut carry = (digit - b'0') as u32;
+        for byte in bytes.iter_mut().rev() {
+            let acc = *byte as u32 * 10 + carry;
+            *byte = acc as u8;
+            carry = acc >> 8;
+        }
+        assert_eq!(carry, 0, "field element {} does not fit in 32 bytes", digits);
+    }
+    bytes
+}
+
+fn byte_const(name: &str, bytes: &[u8]) -> String {
+    format!("pub const {}: [u8; {}] = {};\n\n", name, bytes.len(), byte_array(bytes))
+}
+
+fn byte_array(bytes: &[u8]) -> String {
+    let items: Vec<String> = bytes.iter().map(|b| b.to_string()).collect();
+    format!("[{}]", items.join(", "))
+}
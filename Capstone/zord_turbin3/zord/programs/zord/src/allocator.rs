@@ -0,0 +1,149 @@
+// Bump allocator for the SBF heap, installed with the `custom-heap` feature.
+//
+// The default allocator is fixed to the 32 KiB heap every program gets. This
+// one spans HEAP_FRAME_BYTES, so clients must prepend
+// `ComputeBudgetInstruction::request_heap_frame(HEAP_FRAME_BYTES)` to any
+// transaction calling a custom-heap build; touching memory past the frame
+// that was actually granted aborts the transaction.
+//
+// It also hands memory back when the most recent allocation is freed or
+// grown, which covers the Vec push / temporary buffer patterns in proof
+// deserialization and JSON parsing.
+
+use std::alloc::{GlobalAlloc, Layout};
+use std::mem::size_of;
+use std::ptr::null_mut;
+
+pub const HEAP_START: usize = 0x300000000;
+
+// default heap granted to every program
+pub const DEFAULT_HEAP_BYTES: usize = 32 * 1024;
+
+// largest frame `request_heap_frame` accepts, a multiple of 1 KiB
+pub const HEAP_FRAME_BYTES: usize = 256 * 1024;
+
+// the first two words of the arena hold the cursor and the high-water mark
+const HEADER_LEN: usize = 2 * size_of::<usize>();
+
+#[cfg(all(feature = "custom-heap", target_os = "solana"))]
+#[global_allocator]
+static ALLOCATOR: BumpAllocator = unsafe { BumpAllocator::new(HEAP_START, HEAP_FRAME_BYTES) };
+
+/// Upward-growing bump allocator over `[start, start + len)`.
+///
+/// State lives in the arena itself because SBF programs have no writable
+/// statics; the runtime hands out a zeroed heap, which reads as "empty".
+pub struct BumpAllocator {
+    start: usize,
+    len: usize,
+}
+
+impl BumpAllocator {
+    /// # Safety
+    /// `[start, start + len)` must be writable, zero-initialized memory used by
+    /// nothing else for as long as the allocator or its allocations are alive.
+    pub const unsafe fn new(start: usize, len: usize) -> Self {
+        Self { start, len }
+    }
+
+    /// Bytes handed out at the high-water mark, header excluded.
+    pub fn peak(&self) -> usize {
+        // SAFETY: the header is inside the arena promised in `new`
+        let peak = unsafe { *self.peak_ptr() };
+        peak.saturating_sub(self.start + HEADER_LEN)
+    }
+
+    fn cursor_ptr(&self) -> *mut usize {
+        self.start as *mut usize
+    }
+
+    fn peak_ptr(&self) -> *mut usize {
+        (self.start + size_of::<usize>()) as *mut usize
+    }
+
+    unsafe fn cursor(&self) -> usize {
+        match *self.cursor_ptr() {
+            0 => self.start + HEADER_LEN,
+            pos => pos,
+        }
+    }
+
+    unsafe fn set_cursor(&self, pos: usize) {
+        *self.cursor_ptr() = pos;
+        if pos > *self.peak_ptr() {
+            *self.peak_ptr() = pos;
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let Some(aligned) = self.cursor().checked_add(layout.align() - 1) else {
+            return null_mut();
+        };
+        let ptr = aligned & !(layout.align() - 1);
+        match ptr.checked_add(layout.size()) {
+            Some(end) if end <= self.start + self.len => {
+                self.set_cursor(end);
+                ptr as *mut u8
+            }
+            _ => null_mut(),
+        }
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // only the most recent allocation can be given back
+        if ptr as usize + layout.size() == self.cursor() {
+            *self.cursor_ptr() = ptr as usize;
+        }
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // the most recent allocation grows or shrinks in place
+        if ptr as usize + layout.size() == self.cursor() {
+            return match (ptr as usize).checked_add(new_size) {
+                Some(end) if end <= self.start + self.len => {
+                    self.set_cursor(end);
+                    ptr
+                }
+                _ => null_mut(),
+            };
+        }
+
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            std::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+        }
+        new_ptr
+    }
+}
+
+/// Peak heap usage of the running instruction, for whichever allocator is installed.
+#[cfg(target_os = "solana")]
+pub fn peak_heap_usage() -> usize {
+    #[cfg(feature = "custom-heap")]
+    {
+        ALLOCATOR.peak()
+    }
+
+    // the default allocator keeps its cursor in the first word and bumps down
+    // from the end of the heap without ever freeing
+    #[cfg(not(feature = "custom-heap"))]
+    {
+        let pos = unsafe { *(HEAP_START as *const usize) };
+        match pos {
+            0 => 0,
+            pos => HEAP_START + DEFAULT_HEAP_BYTES - pos,
+        }
+    }
+}
+
+/// Logs `heap peak: <bytes>` when built with `heap-stats`, read by the heap benchmark.
+pub fn log_peak_heap() {
+    #[cfg(all(feature = "heap-stats", target_os = "solana"))]
+    anchor_lang::prelude::msg!("heap peak: {}", peak_heap_usage());
+}
@@ -0,0 +1,6 @@
+use anchor_lang::prelude::*;
+
+// The Arcium MXE program (arcium_module) running the encrypted kyc_match
+// circuit. Its `[b"zord_authority"]` PDA is the only signer zord accepts for
+// `record_arcium_match`, and that program only signs from a verified callback.
+pub const KYC_MATCH_PROGRAM_ID: Pubkey = pubkey!("6NK6Cejzcj2bJMTSQ2WAhaazQnuDUijESQmW2nWhkNDG");
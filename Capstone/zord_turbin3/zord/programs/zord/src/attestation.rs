@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+use crate::errors::ZordError;
+
+// 2 added `document_commitment`
+pub const ATTESTATION_VERSION: u8 = 2;
+
+// document_flags bits
+pub const DOC_PASSPORT: u8 = 1 << 0;
+pub const DOC_PAN: u8 = 1 << 1;
+pub const DOC_ALL: u8 = DOC_PASSPORT | DOC_PAN;
+
+/// Attester-issued statement about `subject`, signed over its Borsh encoding.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Attestation {
+    pub version: u8,
+    pub subject: Pubkey,
+    pub issuer: Pubkey,
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub document_flags: u8,
+    pub claims_commitment: [u8; 32],
+    /// sha3-256 of the salted documents the attester checked, in the Arcium
+    /// `KycMatchInput` encoding; `kyc_match` only scores those documents
+    pub document_commitment: [u8; 32],
+}
+
+impl Attestation {
+    /// sha256 of the canonical (Borsh) encoding.
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        let encoded = self.try_to_vec()?;
+
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&Sha256::digest(&encoded));
+        Ok(hash)
+    }
+
+    pub fn validate(&self, subject: &Pubkey, now: i64) -> Result<()> {
+        require!(
+            self.version == ATTESTATION_VERSION,
+            ZordError::UnsupportedAttestationVersion
+        );
+        require_keys_eq!(self.subject, *subject, ZordError::AttestationSubjectMismatch);
+        require!(
+            self.document_flags != 0 && self.document_flags & !DOC_ALL == 0,
+            ZordError::InvalidAttestation
+        );
+        require!(
+            self.issued_at <= now && self.issued_at < self.expires_at,
+            ZordError::InvalidAttestation
+        );
+        require!(self.expires_at > now, ZordError::AttestationExpired);
+
+        Ok(())
+    }
+}
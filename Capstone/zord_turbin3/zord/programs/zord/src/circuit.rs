@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+use crate::errors::ZordError;
+
+// Public signals of `PassportPanMatch`, in snarkjs order (outputs first, then public inputs)
+pub const VALID_INDEX: usize = 0;
+pub const NULLIFIER_INDEX: usize = 1;
+pub const USER_KEY_COMMITMENT_INDEX: usize = 2;
+pub const NR_PUBLIC_SIGNALS: usize = 3;
+
+/// sha256("zord-user" || user) with the top three bits cleared so it is a BN254 scalar.
+///
+/// Must match `userKeyCommitment` in zk/passport_pan_zk/generate_proof.js.
+pub fn user_key_commitment(user: &Pubkey) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"zord-user");
+    hasher.update(user.as_ref());
+
+    let mut commitment = [0u8; 32];
+    commitment.copy_from_slice(&hasher.finalize());
+    commitment[0] &= 0x1f;
+    commitment
+}
+
+/// sha256 over the concatenated public inputs, as reported in `ZkVerified`.
+pub fn public_inputs_hash(public_inputs: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for input in public_inputs {
+        hasher.update(input);
+    }
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&hasher.finalize());
+    hash
+}
+
+/// The nullifier signal as a PDA seed, empty when the signals are too short
+/// (`check_public_inputs` rejects those).
+///
+/// Poseidon(passport_number, APP_SALT) in the circuit: the same document always
+/// yields the same nullifier, whichever wallet proves it.
+pub fn nullifier_seed(public_inputs: &[[u8; 32]]) -> &[u8] {
+    public_inputs
+        .get(NULLIFIER_INDEX)
+        .map_or(&[], |nullifier| nullifier.as_slice())
+}
+
+/// Checks the proof's public signals claim a successful match made for `user`.
+pub fn check_public_inputs(public_inputs: &[[u8; 32]], user: &Pubkey) -> Result<()> {
+    require!(
+        public_inputs.len() == NR_PUBLIC_SIGNALS,
+        ZordError::PublicInputDeserialize
+    );
+
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    require!(public_inputs[VALID_INDEX] == one, ZordError::VerificationFailed);
+
+    require!(
+        public_inputs[USER_KEY_COMMITMENT_INDEX] == user_key_commitment(user),
+        ZordError::UserKeyMismatch
+    );
+
+    Ok(())
+}
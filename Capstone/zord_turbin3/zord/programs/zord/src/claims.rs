@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::circuit;
+use crate::errors::ZordError;
+
+// Claim ids index the `KycAccount::claims` bitmap
+pub const CLAIM_AGE_OVER_18: u8 = 0;
+pub const CLAIM_RESIDENCY: u8 = 1;
+pub const MAX_CLAIMS: u8 = 64;
+
+// How far a claim circuit's evaluation time may lag behind the cluster clock
+pub const CLAIM_MAX_AGE: i64 = 60 * 60;
+
+/// Meaning of one public signal of a claim circuit, in snarkjs order.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ClaimSignal {
+    /// Circuit output that must be 1
+    Valid,
+    /// `circuit::user_key_commitment` of the signer, binds the proof to the wallet
+    UserKeyCommitment,
+    /// Value pinned at registration, e.g. the minimum age or an ISO 3166 country code
+    Constant([u8; 32]),
+    /// Unix time the claim was evaluated at (e.g. "today" for an age check)
+    Timestamp,
+    /// The document's nullifier, as in `PassportPanMatch`; binds the proof to
+    /// the identity `verify_zk` recorded for the wallet
+    Nullifier,
+}
+
+impl ClaimSignal {
+    // tag + the largest variant
+    pub const SIZE: usize = 1 + 32;
+}
+
+pub fn claim_bit(claim_id: u8) -> Result<u64> {
+    require!(claim_id < MAX_CLAIMS, ZordError::InvalidClaim);
+    Ok(1u64 << claim_id)
+}
+
+/// A layout has to bind the proof to a wallet and to an identity exactly once.
+pub fn validate_layout(layout: &[ClaimSignal], nr_pubinputs: usize) -> Result<()> {
+    let count = |binding: ClaimSignal| layout.iter().filter(|signal| **signal == binding).count();
+    require!(
+        layout.len() == nr_pubinputs
+            && count(ClaimSignal::UserKeyCommitment) == 1
+            && count(ClaimSignal::Nullifier) == 1,
+        ZordError::InvalidClaimLayout
+    );
+    Ok(())
+}
+
+/// Checks each public input against its signal in `layout`. `nullifier` is
+/// the identity `user` verified with.
+pub fn check_public_inputs(
+    layout: &[ClaimSignal],
+    public_inputs: &[[u8; 32]],
+    user: &Pubkey,
+    nullifier: &[u8; 32],
+    now: i64,
+) -> Result<()> {
+    require!(
+        public_inputs.len() == layout.len(),
+        ZordError::PublicInputDeserialize
+    );
+
+    let mut one = [0u8; 32];
+    one[31] = 1;
+
+    for (signal, input) in layout.iter().zip(public_inputs) {
+        match signal {
+            ClaimSignal::Valid => require!(*input == one, ZordError::VerificationFailed),
+            ClaimSignal::UserKeyCommitment => require!(
+                *input == circuit::user_key_commitment(user),
+                ZordError::UserKeyMismatch
+            ),
+            ClaimSignal::Constant(value) => {
+                require!(input == value, ZordError::ClaimInputMismatch)
+            }
+            ClaimSignal::Timestamp => {
+                let at = field_to_i64(input).ok_or(error!(ZordError::StaleClaimTimestamp))?;
+                require!(
+                    at <= now && now - at <= CLAIM_MAX_AGE,
+                    ZordError::StaleClaimTimestamp
+                );
+            }
+            ClaimSignal::Nullifier => {
+                require!(input == nullifier, ZordError::ClaimIdentityMismatch)
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// big-endian field element -> i64, None if it does not fit
+fn field_to_i64(input: &[u8; 32]) -> Option<i64> {
+    if input[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&input[24..]);
+    i64::try_from(u64::from_be_bytes(bytes)).ok()
+}
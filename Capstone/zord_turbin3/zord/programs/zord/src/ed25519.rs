@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use solana_instructions_sysvar::get_instruction_relative;
+use solana_sdk_ids::ed25519_program;
+use crate::errors::ZordError;
+
+// Ed25519SignatureOffsets, see the ed25519 precompile
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const SIGNATURE_LEN: usize = 64;
+const PUBKEY_LEN: usize = 32;
+
+// instruction index the precompile uses for "data lives in this instruction"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Returns the (signer, message) pair checked by the Ed25519 precompile
+/// instruction placed directly before the current instruction.
+///
+/// The precompile has already rejected the transaction if the signature is
+/// invalid, so a well-formed instruction here means the signature holds.
+pub fn load_verified_signature(instructions_sysvar: &AccountInfo) -> Result<(Pubkey, Vec<u8>)> {
+    let ix = get_instruction_relative(-1, instructions_sysvar)
+        .map_err(|_| error!(ZordError::MissingAttesterSignature))?;
+
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        ZordError::MissingAttesterSignature
+    );
+    require!(ix.accounts.is_empty(), ZordError::InvalidAttesterSignature);
+
+    parse_signature(&ix.data)
+}
+
+/// Returns the (signer, message) pair of Ed25519 precompile instruction data
+/// holding exactly one signature, with its key and message inline.
+pub fn parse_signature(data: &[u8]) -> Result<(Pubkey, Vec<u8>)> {
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN && data[0] == 1,
+        ZordError::InvalidAttesterSignature
+    );
+
+    let offsets = &data[SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN];
+    let read_u16 = |i: usize| u16::from_le_bytes([offsets[i], offsets[i + 1]]);
+
+    let signature_offset = read_u16(0) as usize;
+    let signature_ix = read_u16(2);
+    let pubkey_offset = read_u16(4) as usize;
+    let pubkey_ix = read_u16(6);
+    let message_offset = read_u16(8) as usize;
+    let message_size = read_u16(10) as usize;
+    let message_ix = read_u16(12);
+
+    // signature, key and message all have to come from the precompile instruction itself
+    require!(
+        signature_ix == CURRENT_INSTRUCTION
+            && pubkey_ix == CURRENT_INSTRUCTION
+            && message_ix == CURRENT_INSTRUCTION,
+        ZordError::InvalidAttesterSignature
+    );
+    require!(
+        signature_offset + SIGNATURE_LEN <= data.len()
+            && pubkey_offset + PUBKEY_LEN <= data.len()
+            && message_offset + message_size <= data.len(),
+        ZordError::InvalidAttesterSignature
+    );
+
+    let signer = Pubkey::try_from(&data[pubkey_offset..pubkey_offset + PUBKEY_LEN])
+        .map_err(|_| error!(ZordError::InvalidAttesterSignature))?;
+    let message = data[message_offset..message_offset + message_size].to_vec();
+
+    Ok((signer, message))
+}
@@ -25,5 +25,98 @@ pub enum ZordError {
 
     #[msg("ZK proof verification failed")]
     VerificationFailed,
+
+    #[msg("Signer is not the config admin")]
+    Unauthorized,
+
+    #[msg("Verification key version already registered")]
+    VerificationKeyExists,
+
+    #[msg("Verification key has been retired")]
+    VerificationKeyRetired,
+
+    #[msg("Proof is not bound to this user")]
+    UserKeyMismatch,
+
+    #[msg("Attestation is not signed by an attester")]
+    MissingAttesterSignature,
+
+    #[msg("Malformed attester signature instruction")]
+    InvalidAttesterSignature,
+
+    #[msg("Attestation signer is not an allowed attester")]
+    UnknownAttester,
+
+    #[msg("Attester already allowed")]
+    AttesterExists,
+
+    #[msg("Attester allow-list is full")]
+    AttesterListFull,
+
+    #[msg("Unsupported attestation version")]
+    UnsupportedAttestationVersion,
+
+    #[msg("Attestation subject is not the submitting user")]
+    AttestationSubjectMismatch,
+
+    #[msg("Attestation issuer is not the signing attester")]
+    AttestationIssuerMismatch,
+
+    #[msg("Attestation fields are invalid")]
+    InvalidAttestation,
+
+    #[msg("Attestation has expired")]
+    AttestationExpired,
+
+    #[msg("KYC record has been revoked")]
+    KycRevoked,
+
+    #[msg("KYC record is suspended")]
+    KycSuspended,
+
+    #[msg("KYC record is not a verification past its expiry")]
+    KycNotExpired,
+
+    #[msg("Account is not the user's zord KYC account")]
+    InvalidKycAccount,
+
+    #[msg("KYC record is not verified")]
+    KycNotVerified,
+
+    #[msg("KYC verification has expired")]
+    KycExpired,
+
+    #[msg("KYC account already has the current layout")]
+    KycAlreadyMigrated,
+
+    #[msg("Claim id is out of range")]
+    InvalidClaim,
+
+    #[msg("Claim layout must match the key and bind the user and identity exactly once")]
+    InvalidClaimLayout,
+
+    #[msg("Public input does not match the registered claim value")]
+    ClaimInputMismatch,
+
+    #[msg("Claim was evaluated too long ago or in the future")]
+    StaleClaimTimestamp,
+
+    #[msg("Claim has not been verified for this user")]
+    ClaimNotVerified,
+
+    #[msg("Identity is already verified for another wallet")]
+    NullifierAlreadyUsed,
+
+    #[msg("Signer is not the program upgrade authority")]
+    NotUpgradeAuthority,
+
+    #[msg("Attestation does not mark the user as valid")]
+    AttestationNotValid,
+
+    #[msg("Attestation was issued before the suspension it would lift")]
+    StaleAttestation,
+
+    #[msg("Claim proof is not about the identity verified for this wallet")]
+    ClaimIdentityMismatch,
 }
 
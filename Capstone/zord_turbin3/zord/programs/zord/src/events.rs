@@ -1,6 +1,86 @@
 use anchor_lang::prelude::*;
+use crate::state::kyc_account::KycStatus;
+
+// One event per KycAccount state change, so a user's KYC history can be
+// replayed from program logs alone. `timestamp` is the cluster unix time.
+
+#[event]
+pub struct KycInitialized {
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestationSubmitted {
+    pub user: Pubkey,
+    pub attester: Pubkey,
+    pub attestation_hash: [u8; 32],
+    pub status: KycStatus,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ZkVerified {
+    pub user: Pubkey,
+    pub vk_version: u32,
+    pub nullifier: [u8; 32],
+    // sha256 over the concatenated 32-byte public inputs
+    pub public_inputs_hash: [u8; 32],
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KycSuspended {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub reason: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KycRevoked {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub reason: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KycExpired {
+    pub user: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimVerified {
+    pub user: Pubkey,
+    pub claim_id: u8,
+    pub public_inputs_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KycMigrated {
+    pub user: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NullifierReleased {
+    pub nullifier: [u8; 32],
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
 
 #[event]
-pub struct ZkVerifiedEvent {
-    pub verifier: Pubkey,
-}
\ No newline at end of file
+pub struct ArciumMatchRecorded {
+    pub user: Pubkey,
+    pub matched: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::claims;
+use crate::errors::ZordError;
+use crate::state::kyc_account::{KycAccount, KycStatus};
+
+/// Fails unless `kyc_account` is `user`'s `[b"kyc", user]` PDA, owned by zord,
+/// and verified and unexpired as of `now`.
+///
+/// Programs can call this directly on the account instead of going through
+/// the `check_kyc` CPI.
+pub fn assert_kyc_verified(kyc_account: &AccountInfo, user: &Pubkey, now: i64) -> Result<()> {
+    load_verified(kyc_account, user, now).map(|_| ())
+}
+
+/// `assert_kyc_verified`, plus the user must hold `claim_id` (see `claims`).
+pub fn assert_kyc_claim(
+    kyc_account: &AccountInfo,
+    user: &Pubkey,
+    claim_id: u8,
+    now: i64,
+) -> Result<()> {
+    claims::claim_bit(claim_id)?;
+    let kyc = load_verified(kyc_account, user, now)?;
+    require!(kyc.has_claim(claim_id), ZordError::ClaimNotVerified);
+    Ok(())
+}
+
+fn load_verified(kyc_account: &AccountInfo, user: &Pubkey, now: i64) -> Result<KycAccount> {
+    let (expected, _) = Pubkey::find_program_address(&[b"kyc", user.as_ref()], &crate::ID);
+    require_keys_eq!(*kyc_account.key, expected, ZordError::InvalidKycAccount);
+    require_keys_eq!(*kyc_account.owner, crate::ID, ZordError::InvalidKycAccount);
+
+    let data = kyc_account.try_borrow_data()?;
+    let kyc = KycAccount::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(ZordError::InvalidKycAccount))?;
+    require_keys_eq!(kyc.user, *user, ZordError::InvalidKycAccount);
+
+    match kyc.current_status(now) {
+        KycStatus::Verified => Ok(kyc),
+        KycStatus::Pending => err!(ZordError::KycNotVerified),
+        KycStatus::Expired => err!(ZordError::KycExpired),
+        KycStatus::Revoked => err!(ZordError::KycRevoked),
+        KycStatus::Suspended => err!(ZordError::KycSuspended),
+    }
+}
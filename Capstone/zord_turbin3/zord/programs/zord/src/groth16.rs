@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use solana_bn254::prelude::{alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing};
+
+use crate::errors::ZordError;
+
+pub const G1_LEN: usize = 64;
+pub const G2_LEN: usize = 128;
+pub const FIELD_LEN: usize = 32;
+
+// proof layout: A (G1) || B (G2) || C (G1), all big-endian (EIP-197)
+pub const PROOF_LEN: usize = G1_LEN + G2_LEN + G1_LEN;
+
+// BN254 base field modulus (p), big-endian
+const BASE_FIELD_MODULUS: [u8; FIELD_LEN] = [
+    48, 100, 78, 114, 225, 49, 160, 41, 184, 80, 69, 182, 129, 129, 88, 93,
+    151, 129, 106, 145, 104, 113, 202, 141, 60, 32, 140, 22, 216, 124, 253, 71,
+];
+
+// BN254 scalar field modulus (r), big-endian
+const SCALAR_FIELD_MODULUS: [u8; FIELD_LEN] = [
+    48, 100, 78, 114, 225, 49, 160, 41, 184, 80, 69, 182, 129, 129, 88, 93,
+    40, 51, 232, 72, 121, 185, 112, 145, 67, 225, 245, 147, 240, 0, 0, 1,
+];
+
+/// Groth16 verification key in the byte layout the alt_bn128 syscalls expect.
+///
+/// G1 points are `x || y`, G2 points are `x_c1 || x_c0 || y_c1 || y_c0`.
+pub struct Groth16Verifyingkey<'a> {
+    pub nr_pubinputs: usize,
+    pub vk_alpha_g1: [u8; G1_LEN],
+    pub vk_beta_g2: [u8; G2_LEN],
+    pub vk_gamma_g2: [u8; G2_LEN],
+    pub vk_delta_g2: [u8; G2_LEN],
+    pub vk_ic: &'a [[u8; G1_LEN]],
+}
+
+impl Groth16Verifyingkey<'_> {
+    /// Checks the key is shaped for `nr_pubinputs` and every coordinate is canonical.
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.vk_ic.len() == self.nr_pubinputs + 1,
+            ZordError::VerificationKeyDeserialize
+        );
+
+        let points = [
+            &self.vk_alpha_g1[..],
+            &self.vk_beta_g2[..],
+            &self.vk_gamma_g2[..],
+            &self.vk_delta_g2[..],
+        ];
+        let ic = self.vk_ic.iter().map(|p| &p[..]);
+        for point in points.into_iter().chain(ic) {
+            for coord in point.chunks(FIELD_LEN) {
+                require!(
+                    is_less_than(coord, &BASE_FIELD_MODULUS),
+                    ZordError::VerificationKeyDeserialize
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Groth16Proof {
+    pub a: [u8; G1_LEN],
+    pub b: [u8; G2_LEN],
+    pub c: [u8; G1_LEN],
+}
+
+impl Groth16Proof {
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self> {
+        require!(bytes.len() == PROOF_LEN, ZordError::ProofDeserialize);
+
+        let mut proof = Groth16Proof {
+            a: [0u8; G1_LEN],
+            b: [0u8; G2_LEN],
+            c: [0u8; G1_LEN],
+        };
+        proof.a.copy_from_slice(&bytes[..G1_LEN]);
+        proof.b.copy_from_slice(&bytes[G1_LEN..G1_LEN + G2_LEN]);
+        proof.c.copy_from_slice(&bytes[G1_LEN + G2_LEN..]);
+
+        // every coordinate has to be a canonical base field element
+        for coord in bytes.chunks(FIELD_LEN) {
+            require!(
+                is_less_than(coord, &BASE_FIELD_MODULUS),
+                ZordError::ProofDeserialize
+            );
+        }
+
+        Ok(proof)
+    }
+}
+
+/// Runs the Groth16 pairing check
+/// `e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1`.
+pub fn verify(
+    proof: &Groth16Proof,
+    public_inputs: &[[u8; FIELD_LEN]],
+    vk: &Groth16Verifyingkey,
+) -> Result<()> {
+    require!(
+        public_inputs.len() == vk.nr_pubinputs && vk.vk_ic.len() == vk.nr_pubinputs + 1,
+        ZordError::PublicInputDeserialize
+    );
+
+    // vk_x = IC[0] + sum(input_i * IC[i + 1])
+    let mut vk_x = vk.vk_ic[0];
+    for (input, ic) in public_inputs.iter().zip(&vk.vk_ic[1..]) {
+        require!(
+            is_less_than(input, &SCALAR_FIELD_MODULUS),
+            ZordError::PublicInputDeserialize
+        );
+
+        let mul_input = [&ic[..], &input[..]].concat();
+        let product = alt_bn128_multiplication(&mul_input)
+            .map_err(|_| error!(ZordError::VerificationRoutineFailed))?;
+
+        let add_input = [&vk_x[..], &product[..]].concat();
+        let sum = alt_bn128_addition(&add_input)
+            .map_err(|_| error!(ZordError::VerificationRoutineFailed))?;
+
+        vk_x.copy_from_slice(&sum);
+    }
+
+    let pairing_input = [
+        &negate_g1(&proof.a)[..],
+        &proof.b[..],
+        &vk.vk_alpha_g1[..],
+        &vk.vk_beta_g2[..],
+        &vk_x[..],
+        &vk.vk_gamma_g2[..],
+        &proof.c[..],
+        &vk.vk_delta_g2[..],
+    ]
+    .concat();
+
+    let result = alt_bn128_pairing(&pairing_input)
+        .map_err(|_| error!(ZordError::VerificationRoutineFailed))?;
+
+    // the syscall returns 1 as a big-endian u256 when the product is the identity
+    require!(
+        result.len() == FIELD_LEN
+            && result[FIELD_LEN - 1] == 1
+            && result[..FIELD_LEN - 1].iter().all(|b| *b == 0),
+        ZordError::VerificationFailed
+    );
+
+    Ok(())
+}
+
+// -(x, y) = (x, p - y); the point at infinity is its own negation
+fn negate_g1(point: &[u8; G1_LEN]) -> [u8; G1_LEN] {
+    let mut negated = *point;
+    let y = &point[FIELD_LEN..];
+    if y.iter().all(|b| *b == 0) {
+        return negated;
+    }
+
+    let mut borrow = 0i16;
+    for i in (0..FIELD_LEN).rev() {
+        let mut diff = BASE_FIELD_MODULUS[i] as i16 - y[i] as i16 - borrow;
+        borrow = if diff < 0 {
+            diff += 256;
+            1
+        } else {
+            0
+        };
+        negated[FIELD_LEN + i] = diff as u8;
+    }
+
+    negated
+}
+
+// big-endian `a < b`
+fn is_less_than(a: &[u8], b: &[u8; FIELD_LEN]) -> bool {
+    for (x, y) in a.iter().zip(b.iter()) {
+        if x != y {
+            return x < y;
+        }
+    }
+    false
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::state::attester_config::AttesterConfig;
+
+#[derive(Accounts)]
+pub struct AddAttester<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"attesters"],
+        bump = attester_config.bump,
+        has_one = admin @ ZordError::Unauthorized,
+    )]
+    pub attester_config: Account<'info, AttesterConfig>,
+}
+
+pub fn handle(ctx: Context<AddAttester>, attester: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.attester_config;
+
+    require!(!config.is_attester(&attester), ZordError::AttesterExists);
+    require!(
+        config.attesters.len() < AttesterConfig::MAX_ATTESTERS,
+        ZordError::AttesterListFull
+    );
+    config.attesters.push(attester);
+
+    Ok(())
+}
+
+pub fn add_attester(ctx: Context<AddAttester>, attester: Pubkey) -> Result<()> {
+    handle(ctx, attester)
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::gate;
+
+// Read-only claim query, `check_kyc` plus a claim bit; `zord::cpi::check_claim` for other programs.
+#[derive(Accounts)]
+pub struct CheckClaim<'info> {
+    /// CHECK: only used to derive the expected KYC PDA
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: derivation, owner, status and claims are checked by `gate::assert_kyc_claim`
+    pub kyc_account: UncheckedAccount<'info>,
+}
+
+pub fn handle(ctx: Context<CheckClaim>, claim_id: u8) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    gate::assert_kyc_claim(
+        &ctx.accounts.kyc_account.to_account_info(),
+        ctx.accounts.user.key,
+        claim_id,
+        now,
+    )
+}
+
+pub fn check_claim(ctx: Context<CheckClaim>, claim_id: u8) -> Result<()> {
+    handle(ctx, claim_id)
+}
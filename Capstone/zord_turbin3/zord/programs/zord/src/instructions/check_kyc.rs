@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::gate;
+
+// Read-only KYC gate, other programs reach it through `zord::cpi::check_kyc`.
+#[derive(Accounts)]
+pub struct CheckKyc<'info> {
+    /// CHECK: only used to derive the expected KYC PDA
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: derivation, owner and status are checked by `gate::assert_kyc_verified`
+    pub kyc_account: UncheckedAccount<'info>,
+}
+
+pub fn handle(ctx: Context<CheckKyc>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    gate::assert_kyc_verified(
+        &ctx.accounts.kyc_account.to_account_info(),
+        ctx.accounts.user.key,
+        now,
+    )
+}
+
+pub fn check_kyc(ctx: Context<CheckKyc>) -> Result<()> {
+    handle(ctx)
+}
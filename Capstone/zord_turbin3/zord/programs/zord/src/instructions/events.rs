@@ -1,6 +0,0 @@
-use anchor_lang::prelude::*;
-
-#[event]
-pub struct ZkVerifiedEvent {
-    pub verifier: Pubkey,
-}
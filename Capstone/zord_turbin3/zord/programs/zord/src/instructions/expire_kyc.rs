@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::events::KycExpired;
+use crate::state::kyc_account::{KycAccount, KycStatus};
+
+// Permissionless: anyone can persist the expiry of a lapsed verification.
+#[derive(Accounts)]
+pub struct ExpireKyc<'info> {
+    #[account(
+        mut,
+        seeds = [b"kyc", kyc_account.user.as_ref()],
+        bump
+    )]
+    pub kyc_account: Account<'info, KycAccount>,
+}
+
+pub fn handle(ctx: Context<ExpireKyc>) -> Result<()> {
+    let kyc = &mut ctx.accounts.kyc_account;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        kyc.status == KycStatus::Verified && kyc.current_status(now) == KycStatus::Expired,
+        ZordError::KycNotExpired
+    );
+    kyc.status = KycStatus::Expired;
+
+    emit!(KycExpired {
+        user: kyc.user,
+        expires_at: kyc.expires_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn expire_kyc(ctx: Context<ExpireKyc>) -> Result<()> {
+    handle(ctx)
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::program::Zord;
+use crate::state::attester_config::AttesterConfig;
+
+#[derive(Accounts)]
+pub struct InitializeAttesters<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AttesterConfig::SIZE,
+        seeds = [b"attesters"],
+        bump
+    )]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    // same gate as initialize_verifier: the allow-list admin is the upgrade authority
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ ZordError::NotUpgradeAuthority
+    )]
+    pub program: Program<'info, Zord>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ZordError::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<InitializeAttesters>) -> Result<()> {
+    let config = &mut ctx.accounts.attester_config;
+
+    config.admin = ctx.accounts.admin.key();
+    config.bump = ctx.bumps.attester_config;
+    config.attesters = Vec::new();
+
+    Ok(())
+}
+
+pub fn initialize_attesters(ctx: Context<InitializeAttesters>) -> Result<()> {
+    handle(ctx)
+}
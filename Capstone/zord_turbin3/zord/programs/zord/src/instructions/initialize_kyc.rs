@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::kyc_account::KycAccount;
+use crate::events::KycInitialized;
+use crate::state::kyc_account::{
+    KycAccount, KycStatus, KYC_ACCOUNT_VERSION, KYC_RESERVED_LEN, REASON_NONE,
+};
 
 #[derive(Accounts)]
 pub struct InitializeKyc<'info> {
@@ -22,9 +25,23 @@ pub fn handle(ctx: Context<InitializeKyc>) -> Result<()> {
     let kyc = &mut ctx.accounts.kyc_account;
 
     kyc.user = ctx.accounts.user.key();
-    kyc.is_verified = false;
+    kyc.status = KycStatus::Pending;
     kyc.attestation_hash = [0u8; 32];
     kyc.timestamp = 0;
+    kyc.expires_at = 0;
+    kyc.verified_vk_version = 0;
+    kyc.attester = Pubkey::default();
+    kyc.revocation_reason = REASON_NONE;
+    kyc.version = KYC_ACCOUNT_VERSION;
+    kyc.claims = 0;
+    kyc.arcium_match = false;
+    kyc.document_commitment = [0u8; 32];
+    kyc.reserved = [0u8; KYC_RESERVED_LEN];
+
+    emit!(KycInitialized {
+        user: kyc.user,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
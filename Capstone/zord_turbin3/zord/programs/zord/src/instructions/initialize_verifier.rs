@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::program::Zord;
+use crate::state::verifier_config::{VerificationKeyEntry, VerifierConfig};
+use crate::vk::{NR_PUBINPUTS, VERIFYING_KEY};
+
+// the build-time generated passport/PAN key is registered as version 1
+pub const GENESIS_VK_VERSION: u32 = 1;
+
+#[derive(Accounts)]
+pub struct InitializeVerifier<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + VerifierConfig::BASE_SIZE + VerificationKeyEntry::size(NR_PUBINPUTS + 1),
+        seeds = [b"vk"],
+        bump
+    )]
+    pub verifier_config: Account<'info, VerifierConfig>,
+
+    // only the upgrade authority can claim the singleton config and its admin
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ ZordError::NotUpgradeAuthority
+    )]
+    pub program: Program<'info, Zord>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ZordError::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<InitializeVerifier>) -> Result<()> {
+    let config = &mut ctx.accounts.verifier_config;
+
+    config.admin = ctx.accounts.admin.key();
+    config.bump = ctx.bumps.verifier_config;
+    config.keys = vec![VerificationKeyEntry::from_verifying_key(
+        GENESIS_VK_VERSION,
+        &VERIFYING_KEY,
+    )];
+
+    Ok(())
+}
+
+pub fn initialize_verifier(ctx: Context<InitializeVerifier>) -> Result<()> {
+    handle(ctx)
+}
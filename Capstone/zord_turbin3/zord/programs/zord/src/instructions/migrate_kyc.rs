@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+use crate::errors::ZordError;
+use crate::events::KycMigrated;
+use crate::state::kyc_account::{KycAccount, KYC_ACCOUNT_VERSION};
+
+// Grows a v0 KycAccount to the current layout, the user pays the extra rent.
+// `is_verified` becomes the Verified or Pending status.
+#[derive(Accounts)]
+pub struct MigrateKyc<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: may still hold the v0 layout, discriminator and length are checked in `handle`
+    #[account(
+        mut,
+        seeds = [b"kyc", user.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub kyc_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<MigrateKyc>) -> Result<()> {
+    let kyc_info = ctx.accounts.kyc_account.to_account_info();
+    let new_len = 8 + KycAccount::SIZE;
+
+    // 1. Only v0 KycAccounts can be migrated
+    let mut kyc = {
+        let data = kyc_info.try_borrow_data()?;
+        require!(
+            data.starts_with(KycAccount::DISCRIMINATOR),
+            ZordError::InvalidKycAccount
+        );
+        require!(data.len() != new_len, ZordError::KycAlreadyMigrated);
+        KycAccount::from_v0(&data)?
+    };
+
+    // 2. Top the account up to the new rent-exempt minimum
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(kyc_info.lamports());
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: kyc_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    // 3. Grow in place and rewrite the record in the current layout
+    kyc_info.resize(new_len)?;
+    kyc.version = KYC_ACCOUNT_VERSION;
+    kyc.try_serialize(&mut &mut kyc_info.try_borrow_mut_data()?[..])?;
+
+    emit!(KycMigrated {
+        user: ctx.accounts.user.key(),
+        from_version: 0,
+        to_version: KYC_ACCOUNT_VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn migrate_kyc(ctx: Context<MigrateKyc>) -> Result<()> {
+    handle(ctx)
+}
@@ -1,17 +1,55 @@
-// pub mod initialize_kyc;
-// pub mod submit_attestation;
-
-// pub use initialize_kyc::*;
-// pub use submit_attestation::*;
-
-// pub mod verify_zk;
-// pub use verify_zk::*;
-
+// Every instruction module has its own `handle`, so the globs below re-export
+// that name ambiguously; lib.rs calls each one through its full path. The
+// globs themselves stay because Anchor's `#[program]` finds the generated
+// `__client_accounts_*` / `__cpi_client_accounts_*` modules at the crate root.
+#![allow(ambiguous_glob_reexports)]
 
 pub mod initialize_kyc;
 pub mod submit_attestation;
+#[cfg(feature = "json-attestation")]
+pub mod submit_attestation_json;
 pub mod verify_zk;
+pub mod initialize_verifier;
+pub mod register_vk;
+pub mod rotate_vk;
+pub mod retire_vk;
+pub mod initialize_attesters;
+pub mod add_attester;
+pub mod remove_attester;
+pub mod suspend_kyc;
+pub mod revoke_kyc;
+pub mod expire_kyc;
+pub mod check_kyc;
+pub mod migrate_kyc;
+pub mod register_claim;
+pub mod rotate_claim;
+pub mod retire_claim;
+pub mod verify_claim;
+pub mod check_claim;
+pub mod release_nullifier;
+pub mod record_arcium_match;
 
 pub use initialize_kyc::*;
 pub use submit_attestation::*;
+#[cfg(feature = "json-attestation")]
+pub use submit_attestation_json::*;
 pub use verify_zk::*;
+pub use initialize_verifier::*;
+pub use register_vk::*;
+pub use rotate_vk::*;
+pub use retire_vk::*;
+pub use initialize_attesters::*;
+pub use add_attester::*;
+pub use remove_attester::*;
+pub use suspend_kyc::*;
+pub use revoke_kyc::*;
+pub use expire_kyc::*;
+pub use check_kyc::*;
+pub use migrate_kyc::*;
+pub use register_claim::*;
+pub use rotate_claim::*;
+pub use retire_claim::*;
+pub use verify_claim::*;
+pub use check_claim::*;
+pub use release_nullifier::*;
+pub use record_arcium_match::*;
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::arcium::KYC_MATCH_PROGRAM_ID;
+use crate::events::ArciumMatchRecorded;
+use crate::state::kyc_account::KycAccount;
+
+// CPI target for the Arcium kyc_match callback: stores the revealed match bit.
+#[derive(Accounts)]
+pub struct RecordArciumMatch<'info> {
+    #[account(
+        seeds = [b"zord_authority"],
+        bump,
+        seeds::program = KYC_MATCH_PROGRAM_ID
+    )]
+    pub arcium_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"kyc", kyc_account.user.as_ref()],
+        bump
+    )]
+    pub kyc_account: Account<'info, KycAccount>,
+}
+
+pub fn handle(ctx: Context<RecordArciumMatch>, matched: bool) -> Result<()> {
+    let kyc = &mut ctx.accounts.kyc_account;
+
+    kyc.require_not_revoked()?;
+    kyc.arcium_match = matched;
+
+    emit!(ArciumMatchRecorded {
+        user: kyc.user,
+        matched,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn record_arcium_match(ctx: Context<RecordArciumMatch>, matched: bool) -> Result<()> {
+    handle(ctx, matched)
+}
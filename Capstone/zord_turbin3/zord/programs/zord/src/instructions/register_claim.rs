@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::claims::{self, ClaimSignal};
+use crate::errors::ZordError;
+use crate::state::claim_verifier::ClaimVerifier;
+use crate::state::verifier_config::{VerificationKeyEntry, VerificationKeyInput, VerifierConfig};
+
+// Claim keys start at version 1, each claim has its own account
+pub const CLAIM_VK_VERSION: u32 = 1;
+
+#[derive(Accounts)]
+#[instruction(claim_id: u8, layout: Vec<ClaimSignal>, vk: VerificationKeyInput)]
+pub struct RegisterClaim<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vk"],
+        bump = verifier_config.bump,
+        has_one = admin @ ZordError::Unauthorized,
+    )]
+    pub verifier_config: Account<'info, VerifierConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ClaimVerifier::size(layout.len(), vk.vk_ic.len()),
+        seeds = [b"claim", claim_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub claim_verifier: Account<'info, ClaimVerifier>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(
+    ctx: Context<RegisterClaim>,
+    claim_id: u8,
+    layout: Vec<ClaimSignal>,
+    vk: VerificationKeyInput,
+) -> Result<()> {
+    claims::claim_bit(claim_id)?;
+    let key = VerificationKeyEntry::new(CLAIM_VK_VERSION, vk)?;
+    claims::validate_layout(&layout, key.verifying_key()?.nr_pubinputs)?;
+
+    let claim = &mut ctx.accounts.claim_verifier;
+    claim.claim_id = claim_id;
+    claim.bump = ctx.bumps.claim_verifier;
+    claim.layout = layout;
+    claim.key = key;
+
+    Ok(())
+}
+
+pub fn register_claim(
+    ctx: Context<RegisterClaim>,
+    claim_id: u8,
+    layout: Vec<ClaimSignal>,
+    vk: VerificationKeyInput,
+) -> Result<()> {
+    handle(ctx, claim_id, layout, vk)
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::state::verifier_config::{VerificationKeyEntry, VerificationKeyInput, VerifierConfig};
+
+#[derive(Accounts)]
+#[instruction(version: u32, vk: VerificationKeyInput)]
+pub struct RegisterVk<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vk"],
+        bump = verifier_config.bump,
+        has_one = admin @ ZordError::Unauthorized,
+        realloc = verifier_config.to_account_info().data_len()
+            + VerificationKeyEntry::size(vk.vk_ic.len()),
+        realloc::payer = admin,
+        realloc::zero = false,
+    )]
+    pub verifier_config: Account<'info, VerifierConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(
+    ctx: Context<RegisterVk>,
+    version: u32,
+    vk: VerificationKeyInput,
+) -> Result<()> {
+    let entry = VerificationKeyEntry::new(version, vk)?;
+    ctx.accounts.verifier_config.add_key(entry)
+}
+
+pub fn register_vk(
+    ctx: Context<RegisterVk>,
+    version: u32,
+    vk: VerificationKeyInput,
+) -> Result<()> {
+    handle(ctx, version, vk)
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::events::{KycRevoked, NullifierReleased};
+use crate::state::attester_config::AttesterConfig;
+use crate::state::kyc_account::{KycAccount, KycStatus, REASON_USER_REQUEST};
+use crate::state::nullifier_record::NullifierRecord;
+
+/// Frees an identity for a new wallet. The wallet that held it is revoked, so
+/// the document never backs two verified wallets, and gets the record's rent back.
+#[derive(Accounts)]
+pub struct ReleaseNullifier<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"attesters"],
+        bump = attester_config.bump,
+        has_one = admin @ ZordError::Unauthorized,
+    )]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"nullifier", nullifier_record.nullifier.as_ref()],
+        bump = nullifier_record.bump,
+        close = user
+    )]
+    pub nullifier_record: Account<'info, NullifierRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"kyc", user.key().as_ref()],
+        bump
+    )]
+    pub kyc_account: Account<'info, KycAccount>,
+
+    /// CHECK: receives the rent, pinned to the record's wallet
+    #[account(mut, address = nullifier_record.user)]
+    pub user: UncheckedAccount<'info>,
+}
+
+pub fn handle(ctx: Context<ReleaseNullifier>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let admin = ctx.accounts.admin.key();
+    let kyc = &mut ctx.accounts.kyc_account;
+
+    // an earlier revocation keeps its own reason
+    if kyc.status != KycStatus::Revoked {
+        kyc.revoke(REASON_USER_REQUEST);
+
+        emit!(KycRevoked {
+            user: kyc.user,
+            authority: admin,
+            reason: REASON_USER_REQUEST,
+            timestamp: now,
+        });
+    }
+
+    emit!(NullifierReleased {
+        nullifier: ctx.accounts.nullifier_record.nullifier,
+        user: kyc.user,
+        authority: admin,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn release_nullifier(ctx: Context<ReleaseNullifier>) -> Result<()> {
+    handle(ctx)
+}
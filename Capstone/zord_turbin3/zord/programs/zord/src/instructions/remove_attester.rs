@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::state::attester_config::AttesterConfig;
+
+#[derive(Accounts)]
+pub struct RemoveAttester<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"attesters"],
+        bump = attester_config.bump,
+        has_one = admin @ ZordError::Unauthorized,
+    )]
+    pub attester_config: Account<'info, AttesterConfig>,
+}
+
+pub fn handle(ctx: Context<RemoveAttester>, attester: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.attester_config;
+
+    require!(config.is_attester(&attester), ZordError::UnknownAttester);
+    config.attesters.retain(|a| *a != attester);
+
+    Ok(())
+}
+
+pub fn remove_attester(ctx: Context<RemoveAttester>, attester: Pubkey) -> Result<()> {
+    handle(ctx, attester)
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::state::claim_verifier::ClaimVerifier;
+use crate::state::verifier_config::{VerifierConfig, VkStatus};
+
+#[derive(Accounts)]
+#[instruction(claim_id: u8)]
+pub struct RetireClaim<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vk"],
+        bump = verifier_config.bump,
+        has_one = admin @ ZordError::Unauthorized,
+    )]
+    pub verifier_config: Account<'info, VerifierConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", claim_id.to_le_bytes().as_ref()],
+        bump = claim_verifier.bump,
+    )]
+    pub claim_verifier: Account<'info, ClaimVerifier>,
+}
+
+// verify_claim stops accepting proofs until `rotate_claim` registers a new key
+pub fn handle(ctx: Context<RetireClaim>, _claim_id: u8) -> Result<()> {
+    let key = &mut ctx.accounts.claim_verifier.key;
+
+    require!(
+        key.status == VkStatus::Active,
+        ZordError::VerificationKeyRetired
+    );
+    key.status = VkStatus::Retired;
+
+    Ok(())
+}
+
+pub fn retire_claim(ctx: Context<RetireClaim>, claim_id: u8) -> Result<()> {
+    handle(ctx, claim_id)
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::state::verifier_config::{VerifierConfig, VkStatus};
+
+#[derive(Accounts)]
+pub struct RetireVk<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vk"],
+        bump = verifier_config.bump,
+        has_one = admin @ ZordError::Unauthorized,
+    )]
+    pub verifier_config: Account<'info, VerifierConfig>,
+}
+
+pub fn handle(ctx: Context<RetireVk>, version: u32) -> Result<()> {
+    let config = &mut ctx.accounts.verifier_config;
+
+    // retired keys stay in the account so their version can never be reused
+    config.active_key(version)?;
+    config.key_mut(version)?.status = VkStatus::Retired;
+
+    Ok(())
+}
+
+pub fn retire_vk(ctx: Context<RetireVk>, version: u32) -> Result<()> {
+    handle(ctx, version)
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::events::KycRevoked;
+use crate::state::attester_config::AttesterConfig;
+use crate::state::kyc_account::KycAccount;
+
+#[derive(Accounts)]
+pub struct RevokeKyc<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"attesters"],
+        bump = attester_config.bump,
+        constraint = attester_config.is_authority(&authority.key()) @ ZordError::Unauthorized,
+    )]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"kyc", kyc_account.user.as_ref()],
+        bump
+    )]
+    pub kyc_account: Account<'info, KycAccount>,
+}
+
+pub fn handle(ctx: Context<RevokeKyc>, reason: u8) -> Result<()> {
+    let kyc = &mut ctx.accounts.kyc_account;
+
+    kyc.require_not_revoked()?;
+    kyc.revoke(reason);
+
+    emit!(KycRevoked {
+        user: kyc.user,
+        authority: ctx.accounts.authority.key(),
+        reason,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn revoke_kyc(ctx: Context<RevokeKyc>, reason: u8) -> Result<()> {
+    handle(ctx, reason)
+}
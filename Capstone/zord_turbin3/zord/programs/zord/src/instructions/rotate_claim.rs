@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::claims::{self, ClaimSignal};
+use crate::errors::ZordError;
+use crate::state::claim_verifier::ClaimVerifier;
+use crate::state::verifier_config::{VerificationKeyEntry, VerificationKeyInput, VerifierConfig};
+
+#[derive(Accounts)]
+#[instruction(claim_id: u8, layout: Vec<ClaimSignal>, vk: VerificationKeyInput)]
+pub struct RotateClaim<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"vk"],
+        bump = verifier_config.bump,
+        has_one = admin @ ZordError::Unauthorized,
+    )]
+    pub verifier_config: Account<'info, VerifierConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", claim_id.to_le_bytes().as_ref()],
+        bump = claim_verifier.bump,
+        realloc = 8 + ClaimVerifier::size(layout.len(), vk.vk_ic.len()),
+        realloc::payer = admin,
+        realloc::zero = false,
+    )]
+    pub claim_verifier: Account<'info, ClaimVerifier>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Replaces the claim's circuit under the next key version, also after a
+// retirement. Claim bits set under the old key stay until the KYC is revoked.
+pub fn handle(
+    ctx: Context<RotateClaim>,
+    _claim_id: u8,
+    layout: Vec<ClaimSignal>,
+    vk: VerificationKeyInput,
+) -> Result<()> {
+    let claim = &mut ctx.accounts.claim_verifier;
+
+    let key = VerificationKeyEntry::new(claim.key.version + 1, vk)?;
+    claims::validate_layout(&layout, key.verifying_key()?.nr_pubinputs)?;
+
+    claim.layout = layout;
+    claim.key = key;
+
+    Ok(())
+}
+
+pub fn rotate_claim(
+    ctx: Context<RotateClaim>,
+    claim_id: u8,
+    layout: Vec<ClaimSignal>,
+    vk: VerificationKeyInput,
+) -> Result<()> {
+    handle(ctx, claim_id, layout, vk)
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::state::verifier_config::{
+    VerificationKeyEntry, VerificationKeyInput, VerifierConfig, VkStatus,
+};
+
+#[derive(Accounts)]
+#[instruction(old_version: u32, new_version: u32, vk: VerificationKeyInput)]
+pub struct RotateVk<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vk"],
+        bump = verifier_config.bump,
+        has_one = admin @ ZordError::Unauthorized,
+        realloc = verifier_config.to_account_info().data_len()
+            + VerificationKeyEntry::size(vk.vk_ic.len()),
+        realloc::payer = admin,
+        realloc::zero = false,
+    )]
+    pub verifier_config: Account<'info, VerifierConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Registers `new_version` and retires `old_version` in one step.
+pub fn handle(
+    ctx: Context<RotateVk>,
+    old_version: u32,
+    new_version: u32,
+    vk: VerificationKeyInput,
+) -> Result<()> {
+    let config = &mut ctx.accounts.verifier_config;
+
+    config.active_key(old_version)?;
+    let entry = VerificationKeyEntry::new(new_version, vk)?;
+    config.add_key(entry)?;
+    config.key_mut(old_version)?.status = VkStatus::Retired;
+
+    Ok(())
+}
+
+pub fn rotate_vk(
+    ctx: Context<RotateVk>,
+    old_version: u32,
+    new_version: u32,
+    vk: VerificationKeyInput,
+) -> Result<()> {
+    handle(ctx, old_version, new_version, vk)
+}
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
-use sha2::{Digest, Sha256};
+use solana_sdk_ids::sysvar::instructions as instructions_sysvar;
+use crate::allocator;
+use crate::attestation::Attestation;
+use crate::ed25519;
+use crate::events::AttestationSubmitted;
+use crate::state::attester_config::AttesterConfig;
 use crate::state::kyc_account::KycAccount;
 use crate::errors::ZordError;
 
@@ -14,40 +19,101 @@ pub struct SubmitAttestation<'info> {
         bump
     )]
     pub kyc_account: Account<'info, KycAccount>,
+
+    #[account(
+        seeds = [b"attesters"],
+        bump = attester_config.bump
+    )]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    /// CHECK: address is checked, read through the instructions sysvar helpers
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+impl SubmitAttestation<'_> {
+    /// Returns the allow-listed attester that signed `user || attestation_hash`.
+    pub fn verify_attester(&self, attestation_hash: &[u8; 32]) -> Result<Pubkey> {
+        verify_attester(
+            &self.instructions.to_account_info(),
+            &self.attester_config,
+            &self.user.key(),
+            attestation_hash,
+        )
+    }
+}
+
+/// Returns the allow-listed attester whose Ed25519 instruction precedes this one
+/// and signed `user || attestation_hash`.
+pub fn verify_attester(
+    instructions: &AccountInfo,
+    attester_config: &AttesterConfig,
+    user: &Pubkey,
+    attestation_hash: &[u8; 32],
+) -> Result<Pubkey> {
+    let (attester, message) = ed25519::load_verified_signature(instructions)?;
+    require!(
+        attester_config.is_attester(&attester),
+        ZordError::UnknownAttester
+    );
+    require!(
+        message == attestation_message(user, attestation_hash),
+        ZordError::InvalidAttesterSignature
+    );
+
+    Ok(attester)
+}
+
+/// Message an attester signs with Ed25519: `user || attestation_hash`.
+pub fn attestation_message(user: &Pubkey, attestation_hash: &[u8; 32]) -> [u8; 64] {
+    let mut message = [0u8; 64];
+    message[..32].copy_from_slice(user.as_ref());
+    message[32..].copy_from_slice(attestation_hash);
+    message
 }
 
 pub fn handle(
     ctx: Context<SubmitAttestation>,
-    attestation_json: String,
+    attestation: Attestation,
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
 
-    let kyc = &mut ctx.accounts.kyc_account;
+    // 1. Check the fixed fields
+    attestation.validate(&ctx.accounts.user.key(), now)?;
+
+    // 2. Hash the canonical encoding
+    let hash = attestation.hash()?;
 
-    // 1. Hash the JSON
-    let mut hasher = Sha256::new();
-    hasher.update(attestation_json.as_bytes());
-    let hash = hasher.finalize();
+    // 3. Check the attester signature verified by the Ed25519 precompile
+    let attester = ctx.accounts.verify_attester(&hash)?;
+    require_keys_eq!(attestation.issuer, attester, ZordError::AttestationIssuerMismatch);
 
-    // 2. Parse JSON
-    let parsed: serde_json::Value =
-        serde_json::from_str(&attestation_json)
-            .map_err(|_| error!(ZordError::InvalidJson))?;
+    // 4. Write into PDA, a fresh attestation also lifts a suspension
+    let kyc = &mut ctx.accounts.kyc_account;
+    kyc.require_not_revoked()?;
+    kyc.require_fresh_attestation(attestation.issued_at)?;
+    kyc.mark_verified(now, attestation.expires_at);
+    kyc.attestation_hash = hash;
+    kyc.attester = attester;
+    kyc.set_document_commitment(attestation.document_commitment);
 
-    let is_valid = parsed["is_valid"]
-        .as_bool()
-        .ok_or(error!(ZordError::InvalidJson))?;
+    emit!(AttestationSubmitted {
+        user: kyc.user,
+        attester,
+        attestation_hash: hash,
+        status: kyc.status,
+        expires_at: kyc.expires_at,
+        timestamp: now,
+    });
 
-    // 3. Write into PDA
-    kyc.is_verified = is_valid;
-    kyc.timestamp = Clock::get()?.unix_timestamp;
-    kyc.attestation_hash.copy_from_slice(&hash[..32]);
+    allocator::log_peak_heap();
 
     Ok(())
 }
 
 pub fn submit_attestation(
     ctx: Context<SubmitAttestation>,
-    attestation_json: String,
+    attestation: Attestation,
 ) -> Result<()> {
-    handle(ctx, attestation_json)
+    handle(ctx, attestation)
 }
@@ -0,0 +1,104 @@
+// Legacy free-form JSON attestations, only built with the `json-attestation` feature.
+
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+use solana_sdk_ids::sysvar::instructions as instructions_sysvar;
+use crate::allocator;
+use crate::errors::ZordError;
+use crate::events::AttestationSubmitted;
+use crate::instructions::submit_attestation::verify_attester;
+use crate::state::attester_config::AttesterConfig;
+use crate::state::kyc_account::{KycAccount, KycStatus, ZK_VERIFICATION_VALIDITY};
+
+// Same accounts as `SubmitAttestation`, the original `user` and `kyc_account`
+// first. Anchor's generated `cpi::accounts` is keyed by struct name, so
+// sharing it would inherit this feature gate.
+#[derive(Accounts)]
+pub struct SubmitAttestationJson<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"kyc", user.key().as_ref()],
+        bump
+    )]
+    pub kyc_account: Account<'info, KycAccount>,
+
+    #[account(
+        seeds = [b"attesters"],
+        bump = attester_config.bump
+    )]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    /// CHECK: address is checked, read through the instructions sysvar helpers
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+pub fn handle(
+    ctx: Context<SubmitAttestationJson>,
+    attestation_json: String,
+) -> Result<()> {
+
+    // 1. Hash the JSON
+    let mut hasher = Sha256::new();
+    hasher.update(attestation_json.as_bytes());
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&hasher.finalize());
+
+    // 2. Check the attester signature verified by the Ed25519 precompile
+    let attester = verify_attester(
+        &ctx.accounts.instructions.to_account_info(),
+        &ctx.accounts.attester_config,
+        &ctx.accounts.user.key(),
+        &hash,
+    )?;
+
+    // 3. Parse JSON
+    let parsed: serde_json::Value =
+        serde_json::from_str(&attestation_json)
+            .map_err(|_| error!(ZordError::InvalidJson))?;
+
+    let is_valid = parsed["is_valid"]
+        .as_bool()
+        .ok_or(error!(ZordError::InvalidJson))?;
+    require!(is_valid, ZordError::AttestationNotValid);
+
+    // 4. Write into PDA
+    let kyc = &mut ctx.accounts.kyc_account;
+    kyc.require_not_revoked()?;
+    // signed JSON never expires, so lifting a suspension needs its issue time
+    if kyc.status == KycStatus::Suspended {
+        let issued_at = parsed["issued_at"]
+            .as_i64()
+            .ok_or(error!(ZordError::StaleAttestation))?;
+        kyc.require_fresh_attestation(issued_at)?;
+    }
+    let now = Clock::get()?.unix_timestamp;
+    kyc.mark_verified(now, now + ZK_VERIFICATION_VALIDITY);
+    kyc.attestation_hash = hash;
+    kyc.attester = attester;
+    // free-form JSON commits to no documents
+    kyc.set_document_commitment([0u8; 32]);
+
+    emit!(AttestationSubmitted {
+        user: kyc.user,
+        attester,
+        attestation_hash: hash,
+        status: kyc.status,
+        expires_at: kyc.expires_at,
+        timestamp: now,
+    });
+
+    allocator::log_peak_heap();
+
+    Ok(())
+}
+
+pub fn submit_attestation_json(
+    ctx: Context<SubmitAttestationJson>,
+    attestation_json: String,
+) -> Result<()> {
+    handle(ctx, attestation_json)
+}
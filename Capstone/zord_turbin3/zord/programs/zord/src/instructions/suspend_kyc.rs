@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::events::KycSuspended;
+use crate::state::attester_config::AttesterConfig;
+use crate::state::kyc_account::{KycAccount, KycStatus};
+
+#[derive(Accounts)]
+pub struct SuspendKyc<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"attesters"],
+        bump = attester_config.bump,
+        constraint = attester_config.is_authority(&authority.key()) @ ZordError::Unauthorized,
+    )]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"kyc", kyc_account.user.as_ref()],
+        bump
+    )]
+    pub kyc_account: Account<'info, KycAccount>,
+}
+
+pub fn handle(ctx: Context<SuspendKyc>, reason: u8) -> Result<()> {
+    let kyc = &mut ctx.accounts.kyc_account;
+    let now = Clock::get()?.unix_timestamp;
+
+    kyc.require_not_revoked()?;
+    kyc.status = KycStatus::Suspended;
+    kyc.revocation_reason = reason;
+    kyc.timestamp = now;
+
+    emit!(KycSuspended {
+        user: kyc.user,
+        authority: ctx.accounts.authority.key(),
+        reason,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn suspend_kyc(ctx: Context<SuspendKyc>, reason: u8) -> Result<()> {
+    handle(ctx, reason)
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::claims;
+use crate::circuit;
+use crate::errors::ZordError;
+use crate::events::ClaimVerified;
+use crate::groth16::{self, Groth16Proof};
+use crate::state::claim_verifier::ClaimVerifier;
+use crate::state::kyc_account::KycAccount;
+use crate::state::nullifier_record::NullifierRecord;
+use crate::state::verifier_config::VkStatus;
+
+#[derive(Accounts)]
+#[instruction(claim_id: u8)]
+pub struct VerifyClaim<'info> {
+    #[account(
+        seeds = [b"claim", claim_id.to_le_bytes().as_ref()],
+        bump = claim_verifier.bump
+    )]
+    pub claim_verifier: Account<'info, ClaimVerifier>,
+
+    #[account(
+        mut,
+        seeds = [b"kyc", user.key().as_ref()],
+        bump
+    )]
+    pub kyc_account: Account<'info, KycAccount>,
+
+    // The identity verify_zk recorded for this wallet, the claim must be about it
+    #[account(
+        seeds = [b"nullifier", nullifier_record.nullifier.as_ref()],
+        bump = nullifier_record.bump,
+        constraint = nullifier_record.user == user.key() @ ZordError::ClaimIdentityMismatch,
+    )]
+    pub nullifier_record: Account<'info, NullifierRecord>,
+
+    pub user: Signer<'info>,
+}
+
+pub fn handle(
+    ctx: Context<VerifyClaim>,
+    claim_id: u8,
+    proof_bytes: Vec<u8>,
+    public_inputs_bytes: Vec<[u8; 32]>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let claim = &ctx.accounts.claim_verifier;
+    require!(
+        claim.key.status == VkStatus::Active,
+        ZordError::VerificationKeyRetired
+    );
+
+    // 1. The public signals must follow the claim's layout and bind this user
+    //    and their verified identity
+    claims::check_public_inputs(
+        &claim.layout,
+        &public_inputs_bytes,
+        &ctx.accounts.user.key(),
+        &ctx.accounts.nullifier_record.nullifier,
+        now,
+    )?;
+
+    // 2. Pairing check against the claim's own key
+    let vk = claim.key.verifying_key()?;
+    let proof = Groth16Proof::try_from_bytes(&proof_bytes)?;
+    groth16::verify(&proof, &public_inputs_bytes, &vk)?;
+
+    // 3. Record the claim
+    let kyc = &mut ctx.accounts.kyc_account;
+    kyc.require_self_verifiable()?;
+    kyc.claims |= claims::claim_bit(claim_id)?;
+
+    emit!(ClaimVerified {
+        user: kyc.user,
+        claim_id,
+        public_inputs_hash: circuit::public_inputs_hash(&public_inputs_bytes),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn verify_claim(
+    ctx: Context<VerifyClaim>,
+    claim_id: u8,
+    proof_bytes: Vec<u8>,
+    public_inputs_bytes: Vec<[u8; 32]>,
+) -> Result<()> {
+    handle(ctx, claim_id, proof_bytes, public_inputs_bytes)
+}
@@ -1,39 +1,102 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::msg;
+use crate::allocator;
+use crate::circuit;
+use crate::errors::ZordError;
+use crate::events::ZkVerified;
+use crate::groth16::{self, Groth16Proof};
+use crate::state::kyc_account::{KycAccount, ZK_VERIFICATION_VALIDITY};
+use crate::state::nullifier_record::NullifierRecord;
+use crate::state::verifier_config::VerifierConfig;
 
 #[derive(Accounts)]
+#[instruction(vk_version: u32, proof_bytes: Vec<u8>, public_inputs_bytes: Vec<[u8; 32]>)]
 pub struct VerifyZk<'info> {
-    /// CHECK: raw key storage if ever needed
-    #[account(mut)]
-    pub vk_account: AccountInfo<'info>,
+    #[account(
+        seeds = [b"vk"],
+        bump = verifier_config.bump
+    )]
+    pub verifier_config: Account<'info, VerifierConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"kyc", user.key().as_ref()],
+        bump
+    )]
+    pub kyc_account: Account<'info, KycAccount>,
 
-    pub signer: Signer<'info>,
+    // Re-verifying from the owning wallet reuses the record, any other wallet
+    // is turned away in `handle`
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + NullifierRecord::SIZE,
+        seeds = [b"nullifier", circuit::nullifier_seed(&public_inputs_bytes)],
+        bump
+    )]
+    pub nullifier_record: Account<'info, NullifierRecord>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 pub fn handle(
     ctx: Context<VerifyZk>,
+    vk_version: u32,
     proof_bytes: Vec<u8>,
-    _public_inputs_bytes: Vec<[u8; 32]>,
+    public_inputs_bytes: Vec<[u8; 32]>,
 ) -> Result<()> {
-    if proof_bytes.is_empty() {
-        return err!(crate::errors::ZordError::ProofDeserialize);
+    // 1. Load the requested verification key
+    let entry = ctx.accounts.verifier_config.active_key(vk_version)?;
+    let vk = entry.verifying_key()?;
+
+    // 2. The public signals must be a passing match bound to this user
+    circuit::check_public_inputs(&public_inputs_bytes, &ctx.accounts.user.key())?;
+
+    // 3. Deserialize A, B, C
+    let proof = Groth16Proof::try_from_bytes(&proof_bytes)?;
+
+    // 4. Pairing check
+    groth16::verify(&proof, &public_inputs_bytes, &vk)?;
+
+    // 5. One wallet per identity document
+    let now = Clock::get()?.unix_timestamp;
+    let user = ctx.accounts.user.key();
+    let record = &mut ctx.accounts.nullifier_record;
+    if record.user == Pubkey::default() {
+        record.nullifier = public_inputs_bytes[circuit::NULLIFIER_INDEX];
+        record.user = user;
+        record.bump = ctx.bumps.nullifier_record;
+        record.created_at = now;
     }
+    require_keys_eq!(record.user, user, ZordError::NullifierAlreadyUsed);
+
+    // 6. Write into PDA
+    let kyc = &mut ctx.accounts.kyc_account;
+    kyc.require_self_verifiable()?;
+    kyc.mark_verified(now, now + ZK_VERIFICATION_VALIDITY);
+    kyc.verified_vk_version = vk_version;
 
-    // Keccak-256 hash (Solana supported)
-    //let _proof_hash = keccak::hash(&proof_bytes).as_ref();
+    emit!(ZkVerified {
+        user: kyc.user,
+        vk_version,
+        nullifier: record.nullifier,
+        public_inputs_hash: circuit::public_inputs_hash(&public_inputs_bytes),
+        expires_at: kyc.expires_at,
+        timestamp: now,
+    });
 
-    // Emit a simple log instead of using `emit!` macro to avoid procedural macro loading issues
-    msg!("ZK verified by {}", ctx.accounts.signer.key());
+    allocator::log_peak_heap();
 
     Ok(())
 }
 
 pub fn verify_zk(
     ctx: Context<VerifyZk>,
+    vk_version: u32,
     proof_bytes: Vec<u8>,
     public_inputs_bytes: Vec<[u8; 32]>,
 ) -> Result<()> {
-    handle(ctx, proof_bytes, public_inputs_bytes)
+    handle(ctx, vk_version, proof_bytes, public_inputs_bytes)
 }
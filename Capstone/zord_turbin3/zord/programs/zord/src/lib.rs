@@ -5,8 +5,20 @@ pub mod state;
 pub mod errors;
 pub mod events;
 pub mod allocator;  // Custom allocator for memory optimization
+pub mod groth16;
+pub mod circuit;
+pub mod ed25519;
+pub mod attestation;
+pub mod claims;
+pub mod gate;
+pub mod vk;
+pub mod arcium;
 
 use instructions::*;
+#[cfg(not(feature = "json-attestation"))]
+use attestation::Attestation;
+use claims::ClaimSignal;
+use state::verifier_config::VerificationKeyInput;
 
 declare_id!("EgdCU8dmSb3mchFbgchYt28PCk4dnmKrYgQGMJsjMckf");
 
@@ -18,18 +30,130 @@ pub mod zord {
         instructions::initialize_kyc::initialize_kyc(ctx)
     }
 
+    #[cfg(not(feature = "json-attestation"))]
     pub fn submit_attestation(
         ctx: Context<SubmitAttestation>,
+        attestation: Attestation,
+    ) -> Result<()> {
+        instructions::submit_attestation::submit_attestation(ctx, attestation)
+    }
+
+    // The legacy instruction keeps its name and argument, so it replaces the
+    // typed one in builds with the feature
+    #[cfg(feature = "json-attestation")]
+    pub fn submit_attestation(
+        ctx: Context<SubmitAttestationJson>,
         attestation_json: String,
     ) -> Result<()> {
-        instructions::submit_attestation::submit_attestation(ctx, attestation_json)
+        instructions::submit_attestation_json::submit_attestation_json(ctx, attestation_json)
     }
 
     pub fn verify_zk(
         ctx: Context<VerifyZk>,
+        vk_version: u32,
         proof_bytes: Vec<u8>,
         public_inputs_bytes: Vec<[u8; 32]>,
     ) -> Result<()> {
-        instructions::verify_zk::verify_zk(ctx, proof_bytes, public_inputs_bytes)
+        instructions::verify_zk::verify_zk(ctx, vk_version, proof_bytes, public_inputs_bytes)
+    }
+
+    pub fn initialize_verifier(ctx: Context<InitializeVerifier>) -> Result<()> {
+        instructions::initialize_verifier::initialize_verifier(ctx)
+    }
+
+    pub fn register_vk(
+        ctx: Context<RegisterVk>,
+        version: u32,
+        vk: VerificationKeyInput,
+    ) -> Result<()> {
+        instructions::register_vk::register_vk(ctx, version, vk)
+    }
+
+    pub fn rotate_vk(
+        ctx: Context<RotateVk>,
+        old_version: u32,
+        new_version: u32,
+        vk: VerificationKeyInput,
+    ) -> Result<()> {
+        instructions::rotate_vk::rotate_vk(ctx, old_version, new_version, vk)
+    }
+
+    pub fn retire_vk(ctx: Context<RetireVk>, version: u32) -> Result<()> {
+        instructions::retire_vk::retire_vk(ctx, version)
+    }
+
+    pub fn initialize_attesters(ctx: Context<InitializeAttesters>) -> Result<()> {
+        instructions::initialize_attesters::initialize_attesters(ctx)
+    }
+
+    pub fn add_attester(ctx: Context<AddAttester>, attester: Pubkey) -> Result<()> {
+        instructions::add_attester::add_attester(ctx, attester)
+    }
+
+    pub fn remove_attester(ctx: Context<RemoveAttester>, attester: Pubkey) -> Result<()> {
+        instructions::remove_attester::remove_attester(ctx, attester)
+    }
+
+    pub fn suspend_kyc(ctx: Context<SuspendKyc>, reason: u8) -> Result<()> {
+        instructions::suspend_kyc::suspend_kyc(ctx, reason)
+    }
+
+    pub fn revoke_kyc(ctx: Context<RevokeKyc>, reason: u8) -> Result<()> {
+        instructions::revoke_kyc::revoke_kyc(ctx, reason)
+    }
+
+    pub fn expire_kyc(ctx: Context<ExpireKyc>) -> Result<()> {
+        instructions::expire_kyc::expire_kyc(ctx)
+    }
+
+    pub fn check_kyc(ctx: Context<CheckKyc>) -> Result<()> {
+        instructions::check_kyc::check_kyc(ctx)
+    }
+
+    pub fn migrate_kyc(ctx: Context<MigrateKyc>) -> Result<()> {
+        instructions::migrate_kyc::migrate_kyc(ctx)
+    }
+
+    pub fn register_claim(
+        ctx: Context<RegisterClaim>,
+        claim_id: u8,
+        layout: Vec<ClaimSignal>,
+        vk: VerificationKeyInput,
+    ) -> Result<()> {
+        instructions::register_claim::register_claim(ctx, claim_id, layout, vk)
+    }
+
+    pub fn rotate_claim(
+        ctx: Context<RotateClaim>,
+        claim_id: u8,
+        layout: Vec<ClaimSignal>,
+        vk: VerificationKeyInput,
+    ) -> Result<()> {
+        instructions::rotate_claim::rotate_claim(ctx, claim_id, layout, vk)
+    }
+
+    pub fn retire_claim(ctx: Context<RetireClaim>, claim_id: u8) -> Result<()> {
+        instructions::retire_claim::retire_claim(ctx, claim_id)
+    }
+
+    pub fn verify_claim(
+        ctx: Context<VerifyClaim>,
+        claim_id: u8,
+        proof_bytes: Vec<u8>,
+        public_inputs_bytes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::verify_claim::verify_claim(ctx, claim_id, proof_bytes, public_inputs_bytes)
+    }
+
+    pub fn check_claim(ctx: Context<CheckClaim>, claim_id: u8) -> Result<()> {
+        instructions::check_claim::check_claim(ctx, claim_id)
+    }
+
+    pub fn release_nullifier(ctx: Context<ReleaseNullifier>) -> Result<()> {
+        instructions::release_nullifier::release_nullifier(ctx)
+    }
+
+    pub fn record_arcium_match(ctx: Context<RecordArciumMatch>, matched: bool) -> Result<()> {
+        instructions::record_arcium_match::record_arcium_match(ctx, matched)
     }
 }
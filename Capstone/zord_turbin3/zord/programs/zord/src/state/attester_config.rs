@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct AttesterConfig {
+    pub admin: Pubkey,
+    pub bump: u8,
+    pub attesters: Vec<Pubkey>,
+}
+
+impl AttesterConfig {
+    pub const MAX_ATTESTERS: usize = 16;
+
+    pub const SIZE: usize =
+        32 +    // admin
+        1  +    // bump
+        4 + 32 * Self::MAX_ATTESTERS; // attesters
+
+    pub fn is_attester(&self, key: &Pubkey) -> bool {
+        self.attesters.contains(key)
+    }
+
+    /// The admin and every attester may suspend or revoke KYC records.
+    pub fn is_authority(&self, key: &Pubkey) -> bool {
+        *key == self.admin || self.is_attester(key)
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::claims::ClaimSignal;
+use crate::state::verifier_config::VerificationKeyEntry;
+
+// One per claim circuit at [b"claim", claim_id]
+#[account]
+pub struct ClaimVerifier {
+    pub claim_id: u8,
+    pub bump: u8,
+    pub layout: Vec<ClaimSignal>,
+    pub key: VerificationKeyEntry,
+}
+
+impl ClaimVerifier {
+    pub fn size(layout_len: usize, ic_len: usize) -> usize {
+        1 +                                 // claim_id
+        1 +                                 // bump
+        4 + ClaimSignal::SIZE * layout_len + // layout
+        VerificationKeyEntry::size(ic_len)  // key
+    }
+}
@@ -1,17 +1,181 @@
 use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+
+// Layout version written by initialize_kyc / migrate_kyc. v0 accounts predate
+// the `version` byte and hold only the original four fields.
+pub const KYC_ACCOUNT_VERSION: u8 = 1;
+
+// Zeroed bytes kept free so later fields need no realloc
+pub const KYC_RESERVED_LEN: usize = 23;
+
+// ZK verifications carry no expiry of their own
+pub const ZK_VERIFICATION_VALIDITY: i64 = 365 * 24 * 60 * 60;
+
+// revocation_reason codes, 0 while the record is not suspended or revoked
+pub const REASON_NONE: u8 = 0;
+pub const REASON_FRAUD: u8 = 1;
+pub const REASON_DOCUMENT_INVALID: u8 = 2;
+pub const REASON_COMPLIANCE: u8 = 3;
+pub const REASON_USER_REQUEST: u8 = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KycStatus {
+    Pending,
+    Verified,
+    Expired,
+    Revoked,
+    Suspended,
+}
+
+// The v0 fields after the discriminator, see `KycAccount::from_v0`
+#[derive(AnchorDeserialize)]
+struct KycAccountV0 {
+    user: Pubkey,
+    is_verified: bool,
+    attestation_hash: [u8; 32],
+    timestamp: i64,
+}
 
 #[account]
 pub struct KycAccount {
     pub user: Pubkey,
-    pub is_verified: bool,
+    pub status: KycStatus,
     pub attestation_hash: [u8; 32],
+    // last verification, or the suspension while suspended
     pub timestamp: i64,
+    pub expires_at: i64,
+    pub verified_vk_version: u32,
+    pub attester: Pubkey,
+    pub revocation_reason: u8,
+    pub version: u8,
+    // bit `claim_id` is set once that claim's proof verified, see `claims`
+    pub claims: u64,
+    // set by the Arcium kyc_match callback, see `record_arcium_match`
+    pub arcium_match: bool,
+    // from the last typed attestation, zero until one; what the Arcium
+    // kyc_match input must hash to
+    pub document_commitment: [u8; 32],
+    pub reserved: [u8; KYC_RESERVED_LEN],
 }
 
 impl KycAccount {
-    pub const SIZE: usize =
+    // v0 layout, still held by accounts created before versioning: user,
+    // is_verified, attestation_hash, timestamp
+    pub const V0_SIZE: usize =
         32 +    // user
         1  +    // is_verified
         32 +    // attestation_hash
         8;      // timestamp
+
+    pub const SIZE: usize =
+        32 +    // user
+        1  +    // status
+        32 +    // attestation_hash
+        8  +    // timestamp
+        8  +    // expires_at
+        4  +    // verified_vk_version
+        32 +    // attester
+        1  +    // revocation_reason
+        1  +    // version
+        8  +    // claims
+        1  +    // arcium_match
+        32 +    // document_commitment
+        KYC_RESERVED_LEN; // reserved
+
+    /// Reads a v0 account, discriminator included, as the current layout with
+    /// `version` 0. `is_verified` becomes `Verified` or `Pending`; v0 records
+    /// carried no expiry, so a verified one gets `ZK_VERIFICATION_VALIDITY`
+    /// from its timestamp, like `verify_zk`.
+    pub fn from_v0(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() == 8 + Self::V0_SIZE && data.starts_with(Self::DISCRIMINATOR),
+            ZordError::InvalidKycAccount
+        );
+        let v0 = KycAccountV0::deserialize(&mut &data[8..])
+            .map_err(|_| error!(ZordError::InvalidKycAccount))?;
+
+        let (status, expires_at) = if v0.is_verified {
+            let expires_at = v0.timestamp.saturating_add(ZK_VERIFICATION_VALIDITY);
+            (KycStatus::Verified, expires_at)
+        } else {
+            (KycStatus::Pending, 0)
+        };
+        Ok(Self {
+            user: v0.user,
+            status,
+            attestation_hash: v0.attestation_hash,
+            timestamp: v0.timestamp,
+            expires_at,
+            verified_vk_version: 0,
+            attester: Pubkey::default(),
+            revocation_reason: REASON_NONE,
+            version: 0,
+            claims: 0,
+            arcium_match: false,
+            document_commitment: [0u8; 32],
+            reserved: [0u8; KYC_RESERVED_LEN],
+        })
+    }
+
+    /// Status as of `now`: a verification past `expires_at` reads as `Expired`.
+    pub fn current_status(&self, now: i64) -> KycStatus {
+        match self.status {
+            KycStatus::Verified if now >= self.expires_at => KycStatus::Expired,
+            status => status,
+        }
+    }
+
+    pub fn is_verified(&self, now: i64) -> bool {
+        self.current_status(now) == KycStatus::Verified
+    }
+
+    /// Revoked records are terminal.
+    pub fn require_not_revoked(&self) -> Result<()> {
+        require!(self.status != KycStatus::Revoked, ZordError::KycRevoked);
+        Ok(())
+    }
+
+    /// Suspended records can only be cleared by a fresh attester-signed attestation.
+    pub fn require_self_verifiable(&self) -> Result<()> {
+        self.require_not_revoked()?;
+        require!(self.status != KycStatus::Suspended, ZordError::KycSuspended);
+        Ok(())
+    }
+
+    /// Lifting a suspension takes an attestation issued after it, so the user
+    /// cannot replay the one the record was verified with.
+    pub fn require_fresh_attestation(&self, issued_at: i64) -> Result<()> {
+        require!(
+            self.status != KycStatus::Suspended || issued_at > self.timestamp,
+            ZordError::StaleAttestation
+        );
+        Ok(())
+    }
+
+    pub fn has_claim(&self, claim_id: u8) -> bool {
+        claim_id < crate::claims::MAX_CLAIMS && self.claims & (1u64 << claim_id) != 0
+    }
+
+    /// Terminal; the claims were proven for the identity being revoked.
+    pub fn revoke(&mut self, reason: u8) {
+        self.status = KycStatus::Revoked;
+        self.revocation_reason = reason;
+        self.claims = 0;
+    }
+
+    /// Clears `arcium_match` when the commitment changes: a match over other
+    /// documents no longer holds.
+    pub fn set_document_commitment(&mut self, document_commitment: [u8; 32]) {
+        if self.document_commitment != document_commitment {
+            self.arcium_match = false;
+        }
+        self.document_commitment = document_commitment;
+    }
+
+    pub fn mark_verified(&mut self, now: i64, expires_at: i64) {
+        self.status = KycStatus::Verified;
+        self.timestamp = now;
+        self.expires_at = expires_at;
+        self.revocation_reason = REASON_NONE;
+    }
 }
@@ -1,3 +1,11 @@
 pub mod kyc_account;
+pub mod verifier_config;
+pub mod attester_config;
+pub mod claim_verifier;
+pub mod nullifier_record;
 
 pub use kyc_account::*;
+pub use verifier_config::*;
+pub use attester_config::*;
+pub use claim_verifier::*;
+pub use nullifier_record::*;
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+// One per identity document at [b"nullifier", nullifier], created by the
+// first wallet to pass verify_zk with it
+#[account]
+pub struct NullifierRecord {
+    pub nullifier: [u8; 32],
+    pub user: Pubkey,
+    pub bump: u8,
+    pub created_at: i64,
+}
+
+impl NullifierRecord {
+    pub const SIZE: usize =
+        32 +    // nullifier
+        32 +    // user
+        1  +    // bump
+        8;      // created_at
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use crate::errors::ZordError;
+use crate::groth16::{Groth16Verifyingkey, G1_LEN, G2_LEN};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VkStatus {
+    Active,
+    Retired,
+}
+
+/// Verification key as passed to `register_vk` / `rotate_vk`, in the alt_bn128 layout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerificationKeyInput {
+    pub vk_alpha_g1: [u8; G1_LEN],
+    pub vk_beta_g2: [u8; G2_LEN],
+    pub vk_gamma_g2: [u8; G2_LEN],
+    pub vk_delta_g2: [u8; G2_LEN],
+    pub vk_ic: Vec<[u8; G1_LEN]>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerificationKeyEntry {
+    pub version: u32,
+    pub status: VkStatus,
+    pub vk_alpha_g1: [u8; G1_LEN],
+    pub vk_beta_g2: [u8; G2_LEN],
+    pub vk_gamma_g2: [u8; G2_LEN],
+    pub vk_delta_g2: [u8; G2_LEN],
+    pub vk_ic: Vec<[u8; G1_LEN]>,
+}
+
+impl VerificationKeyEntry {
+    pub fn size(ic_len: usize) -> usize {
+        4   +           // version
+        1   +           // status
+        G1_LEN +        // vk_alpha_g1
+        G2_LEN +        // vk_beta_g2
+        G2_LEN +        // vk_gamma_g2
+        G2_LEN +        // vk_delta_g2
+        4 + G1_LEN * ic_len // vk_ic
+    }
+
+    pub fn new(version: u32, vk: VerificationKeyInput) -> Result<Self> {
+        let entry = Self {
+            version,
+            status: VkStatus::Active,
+            vk_alpha_g1: vk.vk_alpha_g1,
+            vk_beta_g2: vk.vk_beta_g2,
+            vk_gamma_g2: vk.vk_gamma_g2,
+            vk_delta_g2: vk.vk_delta_g2,
+            vk_ic: vk.vk_ic,
+        };
+        entry.verifying_key()?;
+        Ok(entry)
+    }
+
+    pub fn from_verifying_key(version: u32, vk: &Groth16Verifyingkey) -> Self {
+        Self {
+            version,
+            status: VkStatus::Active,
+            vk_alpha_g1: vk.vk_alpha_g1,
+            vk_beta_g2: vk.vk_beta_g2,
+            vk_gamma_g2: vk.vk_gamma_g2,
+            vk_delta_g2: vk.vk_delta_g2,
+            vk_ic: vk.vk_ic.to_vec(),
+        }
+    }
+
+    pub fn verifying_key(&self) -> Result<Groth16Verifyingkey<'_>> {
+        require!(!self.vk_ic.is_empty(), ZordError::VerificationKeyDeserialize);
+
+        let vk = Groth16Verifyingkey {
+            nr_pubinputs: self.vk_ic.len() - 1,
+            vk_alpha_g1: self.vk_alpha_g1,
+            vk_beta_g2: self.vk_beta_g2,
+            vk_gamma_g2: self.vk_gamma_g2,
+            vk_delta_g2: self.vk_delta_g2,
+            vk_ic: &self.vk_ic,
+        };
+        vk.validate()?;
+
+        Ok(vk)
+    }
+}
+
+#[account]
+pub struct VerifierConfig {
+    pub admin: Pubkey,
+    pub bump: u8,
+    pub keys: Vec<VerificationKeyEntry>,
+}
+
+impl VerifierConfig {
+    pub const BASE_SIZE: usize =
+        32 +    // admin
+        1  +    // bump
+        4;      // keys (vec prefix)
+
+    pub fn add_key(&mut self, entry: VerificationKeyEntry) -> Result<()> {
+        require!(
+            self.keys.iter().all(|k| k.version != entry.version),
+            ZordError::VerificationKeyExists
+        );
+        self.keys.push(entry);
+        Ok(())
+    }
+
+    pub fn key_mut(&mut self, version: u32) -> Result<&mut VerificationKeyEntry> {
+        self.keys
+            .iter_mut()
+            .find(|k| k.version == version)
+            .ok_or(error!(ZordError::VerificationKeyMissing))
+    }
+
+    /// Returns the active key registered under `version`.
+    pub fn active_key(&self, version: u32) -> Result<&VerificationKeyEntry> {
+        let entry = self
+            .keys
+            .iter()
+            .find(|k| k.version == version)
+            .ok_or(error!(ZordError::VerificationKeyMissing))?;
+
+        require!(entry.status == VkStatus::Active, ZordError::VerificationKeyRetired);
+        Ok(entry)
+    }
+}
@@ -1,9 +1,15 @@
-pub const VK_ALPHA_1: [[u128; 3]; 1] = [
-    [
-        3355201405149005542338752274703656981194145030706213261178506471039253314837_u128,
-        688504195347096316312021453176982399902673482859014584087052168682252916066_u128,
-        1_u128
-    ]
-];
+use crate::groth16::Groth16Verifyingkey;
 
-// ...repeat for beta, gamma, delta, IC
+// VK_ALPHA_G1, VK_BETA_G2, VK_GAMMA_G2, VK_DELTA_G2, VK_IC and NR_PUBINPUTS are
+// generated by build.rs from zk/passport_pan_zk/verification_key.json.
+include!(concat!(env!("OUT_DIR"), "/vk.rs"));
+
+// Verification key of the passport/PAN circuit
+pub const VERIFYING_KEY: Groth16Verifyingkey = Groth16Verifyingkey {
+    nr_pubinputs: NR_PUBINPUTS,
+    vk_alpha_g1: VK_ALPHA_G1,
+    vk_beta_g2: VK_BETA_G2,
+    vk_gamma_g2: VK_GAMMA_G2,
+    vk_delta_g2: VK_DELTA_G2,
+    vk_ic: &VK_IC,
+};
@@ -0,0 +1,65 @@
+// Exercises the `custom-heap` bump allocator on a host-side arena.
+
+use std::alloc::{GlobalAlloc, Layout};
+use zord::allocator::BumpAllocator;
+
+const ARENA_WORDS: usize = 128;
+
+// zeroed like the SBF heap, u64-aligned like HEAP_START
+fn with_arena(test: impl FnOnce(&BumpAllocator, usize)) {
+    let mut arena = vec![0u64; ARENA_WORDS];
+    let start = arena.as_mut_ptr() as usize;
+    let allocator = unsafe { BumpAllocator::new(start, ARENA_WORDS * 8) };
+    test(&allocator, start);
+}
+
+#[test]
+fn allocates_upward_and_aligned() {
+    with_arena(|heap, start| unsafe {
+        let a = heap.alloc(Layout::from_size_align(3, 1).unwrap()) as usize;
+        let b = heap.alloc(Layout::from_size_align(8, 8).unwrap()) as usize;
+
+        assert_eq!(a, start + 16);
+        assert_eq!(b % 8, 0);
+        assert!(b >= a + 3);
+        assert_eq!(heap.peak(), b + 8 - (start + 16));
+    });
+}
+
+#[test]
+fn frees_and_grows_the_last_allocation_in_place() {
+    with_arena(|heap, _| unsafe {
+        let layout = Layout::from_size_align(16, 8).unwrap();
+        let a = heap.alloc(layout);
+        let grown = heap.realloc(a, layout, 64);
+        assert_eq!(grown, a);
+
+        heap.dealloc(grown, Layout::from_size_align(64, 8).unwrap());
+        let again = heap.alloc(layout);
+        assert_eq!(again, a);
+        assert_eq!(heap.peak(), 64);
+    });
+}
+
+#[test]
+fn moves_older_allocations_on_realloc() {
+    with_arena(|heap, _| unsafe {
+        let layout = Layout::from_size_align(8, 8).unwrap();
+        let a = heap.alloc(layout);
+        a.cast::<u64>().write(0xdead_beef);
+        let _b = heap.alloc(layout);
+
+        let moved = heap.realloc(a, layout, 16);
+        assert_ne!(moved, a);
+        assert_eq!(moved.cast::<u64>().read(), 0xdead_beef);
+    });
+}
+
+#[test]
+fn returns_null_when_exhausted() {
+    with_arena(|heap, _| unsafe {
+        let all = Layout::from_size_align(ARENA_WORDS * 8 - 16, 8).unwrap();
+        assert!(!heap.alloc(all).is_null());
+        assert!(heap.alloc(Layout::from_size_align(1, 1).unwrap()).is_null());
+    });
+}
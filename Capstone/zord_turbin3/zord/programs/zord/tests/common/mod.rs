@@ -0,0 +1,693 @@
+// Shared LiteSVM fixtures for the tests that run the SBF build of zord.
+//
+// Accounts are written straight into the bank, and proofs come from a small
+// arkworks circuit with the same public signals as `PassportPanMatch`
+// (valid, nullifier, user_key_commitment), so no circom toolchain is needed.
+
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::instruction::{error::InstructionError, AccountMeta, Instruction};
+use anchor_lang::{AccountSerialize, AnchorSerialize, InstructionData, ToAccountMetas};
+use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+use litesvm::LiteSVM;
+use sha2::{Digest, Sha256};
+use solana_account::Account;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+use zord::attestation::{Attestation, ATTESTATION_VERSION, DOC_ALL};
+use zord::claims::ClaimSignal;
+use zord::errors::ZordError;
+use zord::instructions::submit_attestation::attestation_message;
+use zord::state::attester_config::AttesterConfig;
+use zord::state::kyc_account::{
+    KycAccount, KycStatus, KYC_ACCOUNT_VERSION, KYC_RESERVED_LEN, REASON_NONE,
+};
+use zord::state::verifier_config::{VerificationKeyEntry, VerificationKeyInput, VerifierConfig};
+
+pub const DEPLOY_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy");
+pub const NOW: i64 = 1_700_000_000;
+pub const TEST_VK_VERSION: u32 = 1;
+
+/// `target/deploy/<name>`; panics when it has not been built.
+pub fn deployed_program(name: &str) -> PathBuf {
+    let path = Path::new(DEPLOY_DIR).join(name);
+    assert!(
+        path.exists(),
+        "{} not found, see the build steps at the top of this test",
+        path.display()
+    );
+    path
+}
+
+/// A bank with zord loaded from `program` and the clock at `NOW`.
+pub fn svm_with(program: &Path) -> LiteSVM {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(zord::ID, program).unwrap();
+    set_time(&mut svm, NOW);
+    svm
+}
+
+/// Like `svm_with`, with zord deployed through the upgradeable loader and
+/// `authority` as its upgrade authority, for the instructions gated on it.
+pub fn upgradeable_svm_with(program: &Path, authority: &Pubkey) -> LiteSVM {
+    let mut svm = LiteSVM::new();
+    set_time(&mut svm, NOW);
+
+    // UpgradeableLoaderState::ProgramData { slot, upgrade_authority_address }
+    // in bincode, then the ELF
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(authority.as_ref());
+    data.extend(std::fs::read(program).unwrap());
+    set_loader_account(&mut svm, program_data_pda(), data, false);
+
+    // UpgradeableLoaderState::Program { programdata_address }
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(program_data_pda().as_ref());
+    set_loader_account(&mut svm, zord::ID, data, true);
+    svm
+}
+
+fn set_loader_account(svm: &mut LiteSVM, key: Pubkey, data: Vec<u8>, executable: bool) {
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: bpf_loader_upgradeable::ID,
+        executable,
+        rent_epoch: 0,
+    };
+    svm.set_account(key, account).unwrap();
+}
+
+pub fn program_data_pda() -> Pubkey {
+    Pubkey::find_program_address(&[zord::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn set_time(svm: &mut LiteSVM, unix_timestamp: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp;
+    svm.set_sysvar(&clock);
+}
+
+pub fn funded_keypair(svm: &mut LiteSVM) -> Keypair {
+    let keypair = Keypair::new();
+    svm.airdrop(&keypair.pubkey(), 10_000_000_000).unwrap();
+    keypair
+}
+
+pub fn kyc_pda(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"kyc", user.as_ref()], &zord::ID).0
+}
+
+pub fn vk_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vk"], &zord::ID)
+}
+
+pub fn attesters_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"attesters"], &zord::ID)
+}
+
+/// The nullifier record `verify_zk` uses for these public inputs.
+pub fn nullifier_pda(public_inputs: &[[u8; 32]]) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"nullifier", zord::circuit::nullifier_seed(public_inputs)],
+        &zord::ID,
+    )
+    .0
+}
+
+/// Writes a zord-owned, rent-exempt account holding `value` in `space` bytes.
+pub fn set_zord_account<T: AccountSerialize>(
+    svm: &mut LiteSVM,
+    key: Pubkey,
+    value: &T,
+    space: usize,
+) {
+    let mut data = Vec::with_capacity(space);
+    value.try_serialize(&mut data).unwrap();
+    data.resize(space, 0);
+
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(space),
+        data,
+        owner: zord::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(key, account).unwrap();
+}
+
+pub fn pending_kyc(user: &Pubkey) -> KycAccount {
+    KycAccount {
+        user: *user,
+        status: KycStatus::Pending,
+        attestation_hash: [0u8; 32],
+        timestamp: 0,
+        expires_at: 0,
+        verified_vk_version: 0,
+        attester: Pubkey::default(),
+        revocation_reason: REASON_NONE,
+        version: KYC_ACCOUNT_VERSION,
+        claims: 0,
+        arcium_match: false,
+        document_commitment: [0u8; 32],
+        reserved: [0u8; KYC_RESERVED_LEN],
+    }
+}
+
+pub fn set_kyc(svm: &mut LiteSVM, kyc: &KycAccount) {
+    set_zord_account(svm, kyc_pda(&kyc.user), kyc, 8 + KycAccount::SIZE);
+}
+
+pub fn set_verifier_config(svm: &mut LiteSVM, admin: &Pubkey, keys: Vec<VerificationKeyEntry>) {
+    let (key, bump) = vk_pda();
+    let space = 8
+        + VerifierConfig::BASE_SIZE
+        + keys
+            .iter()
+            .map(|k| VerificationKeyEntry::size(k.vk_ic.len()))
+            .sum::<usize>();
+    let config = VerifierConfig {
+        admin: *admin,
+        bump,
+        keys,
+    };
+    set_zord_account(svm, key, &config, space);
+}
+
+pub fn set_attester_config(svm: &mut LiteSVM, admin: &Pubkey, attesters: Vec<Pubkey>) {
+    let (key, bump) = attesters_pda();
+    let config = AttesterConfig {
+        admin: *admin,
+        bump,
+        attesters,
+    };
+    set_zord_account(svm, key, &config, 8 + AttesterConfig::SIZE);
+}
+
+// mirrors `LiteSVM::send_transaction`, large error variant included
+#[allow(clippy::result_large_err)]
+pub fn send(
+    svm: &mut LiteSVM,
+    instructions: &[Instruction],
+    payer: &Keypair,
+) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    svm.expire_blockhash();
+    result
+}
+
+/// Failure of the instruction at `index` with `error`.
+pub fn zord_error(index: u8, error: ZordError) -> TransactionError {
+    TransactionError::InstructionError(index, InstructionError::Custom(error.into()))
+}
+
+pub fn attestation(user: &Pubkey, issuer: &Pubkey) -> Attestation {
+    Attestation {
+        version: ATTESTATION_VERSION,
+        subject: *user,
+        issuer: *issuer,
+        issued_at: NOW - 60,
+        expires_at: NOW + 365 * 24 * 60 * 60,
+        document_flags: DOC_ALL,
+        claims_commitment: [42u8; 32],
+        document_commitment: [7u8; 32],
+    }
+}
+
+/// Ed25519 precompile instruction for `attester` signing `user || hash`;
+/// it has to sit right before `submit_attestation`.
+pub fn attester_signature(attester: &Keypair, user: &Pubkey, hash: &[u8; 32]) -> Instruction {
+    let message = attestation_message(user, hash);
+    let signature: [u8; 64] = attester.sign_message(&message).into();
+    solana_ed25519_program::new_ed25519_instruction_with_signature(
+        &message,
+        &signature,
+        &attester.pubkey().to_bytes(),
+    )
+}
+
+pub fn initialize_kyc_ix(user: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::InitializeKyc {
+            user: *user,
+            kyc_account: kyc_pda(user),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::InitializeKyc {}.data(),
+    }
+}
+
+pub fn migrate_kyc_ix(user: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::MigrateKyc {
+            user: *user,
+            kyc_account: kyc_pda(user),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::MigrateKyc {}.data(),
+    }
+}
+
+pub fn initialize_verifier_ix(admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::InitializeVerifier {
+            admin: *admin,
+            verifier_config: vk_pda().0,
+            program: zord::ID,
+            program_data: program_data_pda(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::InitializeVerifier {}.data(),
+    }
+}
+
+pub fn initialize_attesters_ix(admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::InitializeAttesters {
+            admin: *admin,
+            attester_config: attesters_pda().0,
+            program: zord::ID,
+            program_data: program_data_pda(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::InitializeAttesters {}.data(),
+    }
+}
+
+pub fn register_vk_ix(admin: &Pubkey, version: u32, vk: VerificationKeyInput) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::RegisterVk {
+            admin: *admin,
+            verifier_config: vk_pda().0,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::RegisterVk { version, vk }.data(),
+    }
+}
+
+pub fn rotate_vk_ix(
+    admin: &Pubkey,
+    old_version: u32,
+    new_version: u32,
+    vk: VerificationKeyInput,
+) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::RotateVk {
+            admin: *admin,
+            verifier_config: vk_pda().0,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::RotateVk {
+            old_version,
+            new_version,
+            vk,
+        }
+        .data(),
+    }
+}
+
+pub fn retire_vk_ix(admin: &Pubkey, version: u32) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::RetireVk {
+            admin: *admin,
+            verifier_config: vk_pda().0,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::RetireVk { version }.data(),
+    }
+}
+
+pub fn suspend_kyc_ix(authority: &Pubkey, user: &Pubkey, reason: u8) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::SuspendKyc {
+            authority: *authority,
+            attester_config: attesters_pda().0,
+            kyc_account: kyc_pda(user),
+        }
+        .to_account_metas(None),
+        data: zord::instruction::SuspendKyc { reason }.data(),
+    }
+}
+
+pub fn revoke_kyc_ix(authority: &Pubkey, user: &Pubkey, reason: u8) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::RevokeKyc {
+            authority: *authority,
+            attester_config: attesters_pda().0,
+            kyc_account: kyc_pda(user),
+        }
+        .to_account_metas(None),
+        data: zord::instruction::RevokeKyc { reason }.data(),
+    }
+}
+
+pub fn expire_kyc_ix(user: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::ExpireKyc {
+            kyc_account: kyc_pda(user),
+        }
+        .to_account_metas(None),
+        data: zord::instruction::ExpireKyc {}.data(),
+    }
+}
+
+pub fn claim_pda(claim_id: u8) -> Pubkey {
+    Pubkey::find_program_address(&[b"claim", &[claim_id]], &zord::ID).0
+}
+
+pub fn register_claim_ix(
+    admin: &Pubkey,
+    claim_id: u8,
+    layout: Vec<ClaimSignal>,
+    vk: VerificationKeyInput,
+) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::RegisterClaim {
+            admin: *admin,
+            verifier_config: vk_pda().0,
+            claim_verifier: claim_pda(claim_id),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::RegisterClaim {
+            claim_id,
+            layout,
+            vk,
+        }
+        .data(),
+    }
+}
+
+pub fn rotate_claim_ix(
+    admin: &Pubkey,
+    claim_id: u8,
+    layout: Vec<ClaimSignal>,
+    vk: VerificationKeyInput,
+) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::RotateClaim {
+            admin: *admin,
+            verifier_config: vk_pda().0,
+            claim_verifier: claim_pda(claim_id),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::RotateClaim {
+            claim_id,
+            layout,
+            vk,
+        }
+        .data(),
+    }
+}
+
+pub fn retire_claim_ix(admin: &Pubkey, claim_id: u8) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::RetireClaim {
+            admin: *admin,
+            verifier_config: vk_pda().0,
+            claim_verifier: claim_pda(claim_id),
+        }
+        .to_account_metas(None),
+        data: zord::instruction::RetireClaim { claim_id }.data(),
+    }
+}
+
+/// `verify_claim` for the identity `user` verified with under `nullifier`.
+pub fn verify_claim_ix(
+    user: &Pubkey,
+    claim_id: u8,
+    nullifier: &[u8; 32],
+    proof_bytes: Vec<u8>,
+    public_inputs_bytes: Vec<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::VerifyClaim {
+            claim_verifier: claim_pda(claim_id),
+            kyc_account: kyc_pda(user),
+            nullifier_record: Pubkey::find_program_address(&[b"nullifier", nullifier], &zord::ID).0,
+            user: *user,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::VerifyClaim {
+            claim_id,
+            proof_bytes,
+            public_inputs_bytes,
+        }
+        .data(),
+    }
+}
+
+pub fn check_claim_ix(user: &Pubkey, claim_id: u8) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::CheckClaim {
+            user: *user,
+            kyc_account: kyc_pda(user),
+        }
+        .to_account_metas(None),
+        data: zord::instruction::CheckClaim { claim_id }.data(),
+    }
+}
+
+// Spelled out rather than through `zord::instruction`: with `json-attestation`
+// the crate's `submit_attestation` is the legacy JSON one, while these tests
+// still run the typed build in zord.so
+pub fn submit_attestation_ix(user: &Pubkey, attestation: Attestation) -> Instruction {
+    let mut data = Sha256::digest(b"global:submit_attestation")[..8].to_vec();
+    data.extend(attestation.try_to_vec().unwrap());
+    Instruction {
+        program_id: zord::ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(kyc_pda(user), false),
+            AccountMeta::new_readonly(attesters_pda().0, false),
+            AccountMeta::new_readonly(solana_sdk_ids::sysvar::instructions::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn verify_zk_ix(
+    user: &Pubkey,
+    vk_version: u32,
+    proof_bytes: Vec<u8>,
+    public_inputs_bytes: Vec<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::VerifyZk {
+            verifier_config: vk_pda().0,
+            kyc_account: kyc_pda(user),
+            nullifier_record: nullifier_pda(&public_inputs_bytes),
+            user: *user,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::VerifyZk {
+            vk_version,
+            proof_bytes,
+            public_inputs_bytes,
+        }
+        .data(),
+    }
+}
+
+pub fn release_nullifier_ix(admin: &Pubkey, user: &Pubkey, nullifier: &[u8; 32]) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::ReleaseNullifier {
+            admin: *admin,
+            attester_config: attesters_pda().0,
+            nullifier_record: Pubkey::find_program_address(&[b"nullifier", nullifier], &zord::ID).0,
+            kyc_account: kyc_pda(user),
+            user: *user,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::ReleaseNullifier {}.data(),
+    }
+}
+
+/// `record_arcium_match` signed by `authority`, which only the Arcium
+/// program's `[b"zord_authority"]` PDA should be able to be.
+pub fn record_arcium_match_ix(authority: &Pubkey, user: &Pubkey, matched: bool) -> Instruction {
+    Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::RecordArciumMatch {
+            arcium_authority: *authority,
+            kyc_account: kyc_pda(user),
+        }
+        .to_account_metas(None),
+        data: zord::instruction::RecordArciumMatch { matched }.data(),
+    }
+}
+
+pub mod zk {
+    use ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine};
+    use ark_ff::{BigInteger, PrimeField};
+    use ark_groth16::{Groth16, ProvingKey};
+    use ark_relations::lc;
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+    use ark_snark::SNARK;
+    use ark_std::rand::rngs::StdRng;
+    use ark_std::rand::SeedableRng;
+
+    use anchor_lang::prelude::Pubkey;
+    use zord::circuit;
+    use zord::state::verifier_config::{VerificationKeyEntry, VerificationKeyInput};
+
+    // Any number of public signals, each tied to a witness by `x * x == x_sq`.
+    // What the signals mean is up to zord, e.g. (valid, nullifier,
+    // user_key_commitment) for `verify_zk`.
+    #[derive(Clone)]
+    struct SignalsCircuit {
+        inputs: Vec<Fr>,
+    }
+
+    impl ConstraintSynthesizer<Fr> for SignalsCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            for value in self.inputs {
+                let input = cs.new_input_variable(|| Ok(value))?;
+                let square = cs.new_witness_variable(|| Ok(value * value))?;
+                cs.enforce_constraint(lc!() + input, lc!() + input, lc!() + square)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Deterministic Groth16 setup for the test circuit.
+    pub struct Prover {
+        pk: ProvingKey<Bn254>,
+        rng: StdRng,
+    }
+
+    impl Prover {
+        /// Prover for the `verify_zk` layout (valid, nullifier, user_key_commitment).
+        pub fn new(seed: u64) -> Self {
+            Self::with_inputs(seed, circuit::NR_PUBLIC_SIGNALS)
+        }
+
+        pub fn with_inputs(seed: u64, nr_inputs: usize) -> Self {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let blank = SignalsCircuit {
+                inputs: vec![Fr::from(0u64); nr_inputs],
+            };
+            let (pk, _) = Groth16::<Bn254>::circuit_specific_setup(blank, &mut rng).unwrap();
+            Self { pk, rng }
+        }
+
+        pub fn vk_input(&self) -> VerificationKeyInput {
+            let vk = &self.pk.vk;
+            VerificationKeyInput {
+                vk_alpha_g1: g1(&vk.alpha_g1),
+                vk_beta_g2: g2(&vk.beta_g2),
+                vk_gamma_g2: g2(&vk.gamma_g2),
+                vk_delta_g2: g2(&vk.delta_g2),
+                vk_ic: vk.gamma_abc_g1.iter().map(g1).collect(),
+            }
+        }
+
+        pub fn vk_entry(&self, version: u32) -> VerificationKeyEntry {
+            VerificationKeyEntry::new(version, self.vk_input()).unwrap()
+        }
+
+        /// Proof bytes and public inputs in the `verify_zk` layout, bound to
+        /// `user` and to an identity of its own.
+        pub fn prove(&mut self, user: &Pubkey) -> (Vec<u8>, Vec<[u8; 32]>) {
+            self.prove_identity(user, &nullifier(user))
+        }
+
+        /// Like `prove`, for the identity behind `nullifier`.
+        pub fn prove_identity(
+            &mut self,
+            user: &Pubkey,
+            nullifier: &[u8; 32],
+        ) -> (Vec<u8>, Vec<[u8; 32]>) {
+            let inputs = vec![field(1), *nullifier, circuit::user_key_commitment(user)];
+            (self.prove_inputs(&inputs), inputs)
+        }
+
+        /// Proof bytes for arbitrary 32-byte big-endian public inputs.
+        pub fn prove_inputs(&mut self, inputs: &[[u8; 32]]) -> Vec<u8> {
+            let witness = SignalsCircuit {
+                inputs: inputs
+                    .iter()
+                    .map(|i| Fr::from_be_bytes_mod_order(i))
+                    .collect(),
+            };
+            let proof = Groth16::<Bn254>::prove(&self.pk, witness, &mut self.rng).unwrap();
+            [&g1(&proof.a)[..], &g2(&proof.b)[..], &g1(&proof.c)[..]].concat()
+        }
+    }
+
+    /// `value` as a 32-byte big-endian public input.
+    pub fn field(value: u64) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[24..].copy_from_slice(&value.to_be_bytes());
+        bytes
+    }
+
+    /// A distinct scalar per user, standing in for Poseidon(passport_number, APP_SALT).
+    pub fn nullifier(user: &Pubkey) -> [u8; 32] {
+        let mut bytes = user.to_bytes();
+        bytes[0] &= 0x1f;
+        bytes
+    }
+
+    fn fq(value: &Fq) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&value.into_bigint().to_bytes_be());
+        bytes
+    }
+
+    fn g1(point: &G1Affine) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&fq(&point.x));
+        bytes[32..].copy_from_slice(&fq(&point.y));
+        bytes
+    }
+
+    // EIP-197: x_c1 || x_c0 || y_c1 || y_c0
+    fn g2(point: &G2Affine) -> [u8; 128] {
+        let mut bytes = [0u8; 128];
+        bytes[..32].copy_from_slice(&fq(&point.x.c1));
+        bytes[32..64].copy_from_slice(&fq(&point.x.c0));
+        bytes[64..96].copy_from_slice(&fq(&point.y.c1));
+        bytes[96..].copy_from_slice(&fq(&point.y.c0));
+        bytes
+    }
+}
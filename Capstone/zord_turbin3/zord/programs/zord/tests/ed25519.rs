@@ -0,0 +1,82 @@
+// Reads Ed25519 precompile instruction data as the SDK builds it, and rejects
+// anything that would let the key or message come from somewhere else.
+
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use zord::ed25519::parse_signature;
+use zord::errors::ZordError;
+
+// Ed25519SignatureOffsets fields, as byte offsets into the instruction data
+const SIGNATURE_IX: usize = 4;
+const PUBKEY_IX: usize = 8;
+const MESSAGE_SIZE: usize = 12;
+const MESSAGE_IX: usize = 14;
+
+const MESSAGE: &[u8] = b"user || attestation hash";
+
+fn signed(signer: &Keypair) -> Vec<u8> {
+    let signature: [u8; 64] = signer.sign_message(MESSAGE).into();
+    solana_ed25519_program::new_ed25519_instruction_with_signature(
+        MESSAGE,
+        &signature,
+        &signer.pubkey().to_bytes(),
+    )
+    .data
+}
+
+fn set_u16(data: &mut [u8], at: usize, value: u16) {
+    data[at..at + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn assert_invalid(data: &[u8]) {
+    assert_eq!(
+        parse_signature(data).unwrap_err(),
+        ZordError::InvalidAttesterSignature.into()
+    );
+}
+
+#[test]
+fn reads_the_signer_and_message() {
+    let signer = Keypair::new();
+    let (key, message) = parse_signature(&signed(&signer)).unwrap();
+    assert_eq!(key, signer.pubkey());
+    assert_eq!(message, MESSAGE);
+}
+
+#[test]
+fn needs_exactly_one_signature() {
+    let mut data = signed(&Keypair::new());
+    for count in [0, 2] {
+        data[0] = count;
+        assert_invalid(&data);
+    }
+}
+
+#[test]
+fn rejects_truncated_data() {
+    let data = signed(&Keypair::new());
+    assert_invalid(&data[..15]);
+    assert_invalid(&data[..data.len() - 1]);
+    assert_invalid(&[]);
+}
+
+#[test]
+fn rejects_data_from_another_instruction() {
+    for field in [SIGNATURE_IX, PUBKEY_IX, MESSAGE_IX] {
+        let mut data = signed(&Keypair::new());
+        set_u16(&mut data, field, 0);
+        assert_invalid(&data);
+    }
+}
+
+#[test]
+fn rejects_a_message_past_the_end() {
+    let mut data = signed(&Keypair::new());
+    set_u16(&mut data, MESSAGE_SIZE, MESSAGE.len() as u16 + 1);
+    assert_invalid(&data);
+
+    // a shorter size is well-formed, the caller compares the message itself
+    set_u16(&mut data, MESSAGE_SIZE, 4);
+    let (_, message) = parse_signature(&data).unwrap();
+    assert_eq!(message, &MESSAGE[..4]);
+}
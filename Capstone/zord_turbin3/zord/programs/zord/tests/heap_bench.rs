@@ -0,0 +1,106 @@
+// Peak heap and compute units of submit_attestation and verify_zk, with the
+// default allocator and with the `custom-heap` bump allocator. Needs two SBF
+// builds with heap logging turned on:
+//
+//   anchor build -p zord -- --features heap-stats
+//   cp target/deploy/zord.so target/deploy/zord_heap_stats.so
+//   anchor build -p zord -- --features heap-stats,custom-heap
+//   cp target/deploy/zord.so target/deploy/zord_custom_heap.so
+//
+// then `cargo test -p zord --test heap_bench -- --ignored --nocapture` prints
+// the table.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use common::zk::Prover;
+use common::*;
+use litesvm::types::TransactionMetadata;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_signer::Signer;
+use zord::allocator::{DEFAULT_HEAP_BYTES, HEAP_FRAME_BYTES};
+
+const CU_LIMIT: u32 = 1_400_000;
+
+struct Sample {
+    compute_units: u64,
+    peak_heap: usize,
+}
+
+fn sample(meta: &TransactionMetadata) -> Sample {
+    let peak_heap = meta
+        .logs
+        .iter()
+        .find_map(|log| log.strip_prefix("Program log: heap peak: "))
+        .expect("program was not built with heap-stats")
+        .parse()
+        .unwrap();
+    Sample {
+        compute_units: meta.compute_units_consumed,
+        peak_heap,
+    }
+}
+
+// runs both instructions for a fresh user, returns (submit_attestation, verify_zk)
+fn run(program: &std::path::Path, heap_frame: Option<usize>) -> (Sample, Sample) {
+    let mut svm = svm_with(program);
+    let admin = Pubkey::new_unique();
+    let user = funded_keypair(&mut svm);
+    let attester = funded_keypair(&mut svm);
+    let mut prover = Prover::new(7);
+
+    set_attester_config(&mut svm, &admin, vec![attester.pubkey()]);
+    set_verifier_config(&mut svm, &admin, vec![prover.vk_entry(TEST_VK_VERSION)]);
+    set_kyc(&mut svm, &pending_kyc(&user.pubkey()));
+
+    let mut budget = vec![ComputeBudgetInstruction::set_compute_unit_limit(CU_LIMIT)];
+    if let Some(bytes) = heap_frame {
+        budget.push(ComputeBudgetInstruction::request_heap_frame(bytes as u32));
+    }
+    let with_budget = |ixs: Vec<Instruction>| [budget.clone(), ixs].concat();
+
+    let attestation = attestation(&user.pubkey(), &attester.pubkey());
+    let hash = attestation.hash().unwrap();
+    let submit = send(
+        &mut svm,
+        &with_budget(vec![
+            attester_signature(&attester, &user.pubkey(), &hash),
+            submit_attestation_ix(&user.pubkey(), attestation),
+        ]),
+        &user,
+    )
+    .expect("submit_attestation failed");
+
+    let (proof, inputs) = prover.prove(&user.pubkey());
+    let verify = send(
+        &mut svm,
+        &with_budget(vec![verify_zk_ix(&user.pubkey(), TEST_VK_VERSION, proof, inputs)]),
+        &user,
+    )
+    .expect("verify_zk failed");
+
+    (sample(&submit), sample(&verify))
+}
+
+#[test]
+#[ignore = "needs the heap-stats SBF builds, see the top of this file"]
+fn heap_and_compute_with_and_without_custom_heap() {
+    let default_so = deployed_program("zord_heap_stats.so");
+    let custom_so = deployed_program("zord_custom_heap.so");
+
+    let default = run(&default_so, None);
+    let custom = run(&custom_so, Some(HEAP_FRAME_BYTES));
+
+    println!("{:<20} {:<12} {:>10} {:>12}", "instruction", "allocator", "CU", "peak heap");
+    for (name, default, custom) in [
+        ("submit_attestation", &default.0, &custom.0),
+        ("verify_zk", &default.1, &custom.1),
+    ] {
+        println!("{:<20} {:<12} {:>10} {:>12}", name, "default", default.compute_units, default.peak_heap);
+        println!("{:<20} {:<12} {:>10} {:>12}", name, "custom-heap", custom.compute_units, custom.peak_heap);
+
+        assert!(default.peak_heap > 0 && default.peak_heap <= DEFAULT_HEAP_BYTES);
+        assert!(custom.peak_heap > 0 && custom.peak_heap <= HEAP_FRAME_BYTES);
+    }
+}
@@ -0,0 +1,169 @@
+// The checks zord's instructions make on KycAccount and VerifierConfig, run on
+// the host without the SBF build.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{pending_kyc, NOW};
+use zord::errors::ZordError;
+use zord::groth16::{G1_LEN, G2_LEN};
+use zord::state::kyc_account::{KycAccount, KycStatus, REASON_FRAUD};
+use zord::state::verifier_config::{VerificationKeyEntry, VerifierConfig, VkStatus};
+
+fn verified(expires_at: i64) -> KycAccount {
+    let mut kyc = pending_kyc(&Pubkey::new_unique());
+    kyc.mark_verified(NOW - 60, expires_at);
+    kyc
+}
+
+fn suspended_at(timestamp: i64) -> KycAccount {
+    let mut kyc = verified(NOW + 60);
+    kyc.status = KycStatus::Suspended;
+    kyc.timestamp = timestamp;
+    kyc
+}
+
+#[test]
+fn verification_reads_as_expired_from_expires_at() {
+    let kyc = verified(NOW);
+    assert_eq!(kyc.current_status(NOW - 1), KycStatus::Verified);
+    assert!(kyc.is_verified(NOW - 1));
+    assert_eq!(kyc.current_status(NOW), KycStatus::Expired);
+    assert!(!kyc.is_verified(NOW));
+}
+
+#[test]
+fn only_a_verification_expires() {
+    for status in [
+        KycStatus::Pending,
+        KycStatus::Expired,
+        KycStatus::Revoked,
+        KycStatus::Suspended,
+    ] {
+        let mut kyc = verified(NOW);
+        kyc.status = status;
+        assert_eq!(kyc.current_status(NOW + 1), status);
+    }
+}
+
+#[test]
+fn lifting_a_suspension_needs_a_later_attestation() {
+    let kyc = suspended_at(NOW);
+    assert_eq!(
+        kyc.require_fresh_attestation(NOW).unwrap_err(),
+        ZordError::StaleAttestation.into()
+    );
+    assert_eq!(
+        kyc.require_fresh_attestation(NOW - 1).unwrap_err(),
+        ZordError::StaleAttestation.into()
+    );
+    kyc.require_fresh_attestation(NOW + 1).unwrap();
+
+    // outside a suspension any attestation the other checks accept will do
+    verified(NOW + 60)
+        .require_fresh_attestation(NOW - 3600)
+        .unwrap();
+    pending_kyc(&Pubkey::new_unique())
+        .require_fresh_attestation(0)
+        .unwrap();
+}
+
+#[test]
+fn revoked_and_suspended_records_stay_out_of_self_service() {
+    let suspended = suspended_at(NOW);
+    suspended.require_not_revoked().unwrap();
+    assert_eq!(
+        suspended.require_self_verifiable().unwrap_err(),
+        ZordError::KycSuspended.into()
+    );
+
+    let mut revoked = verified(NOW + 60);
+    revoked.claims = 0b101;
+    revoked.revoke(REASON_FRAUD);
+    assert_eq!(
+        (revoked.status, revoked.revocation_reason, revoked.claims),
+        (KycStatus::Revoked, REASON_FRAUD, 0)
+    );
+    assert_eq!(
+        revoked.require_not_revoked().unwrap_err(),
+        ZordError::KycRevoked.into()
+    );
+    assert_eq!(
+        revoked.require_self_verifiable().unwrap_err(),
+        ZordError::KycRevoked.into()
+    );
+}
+
+#[test]
+fn new_documents_clear_the_arcium_match() {
+    let mut kyc = verified(NOW + 60);
+    kyc.set_document_commitment([7u8; 32]);
+    kyc.arcium_match = true;
+
+    // the same documents attested again keep it
+    kyc.set_document_commitment([7u8; 32]);
+    assert!(kyc.arcium_match);
+
+    kyc.set_document_commitment([8u8; 32]);
+    assert_eq!(kyc.document_commitment, [8u8; 32]);
+    assert!(!kyc.arcium_match);
+}
+
+fn key(version: u32, status: VkStatus) -> VerificationKeyEntry {
+    VerificationKeyEntry {
+        version,
+        status,
+        vk_alpha_g1: [0u8; G1_LEN],
+        vk_beta_g2: [0u8; G2_LEN],
+        vk_gamma_g2: [0u8; G2_LEN],
+        vk_delta_g2: [0u8; G2_LEN],
+        vk_ic: vec![[0u8; G1_LEN]; 4],
+    }
+}
+
+// VerificationKeyEntry has no Debug, so no `unwrap_err`
+fn error<T>(result: anchor_lang::Result<T>) -> anchor_lang::error::Error {
+    match result {
+        Ok(_) => panic!("expected an error"),
+        Err(error) => error,
+    }
+}
+
+fn config(keys: Vec<VerificationKeyEntry>) -> VerifierConfig {
+    VerifierConfig {
+        admin: Pubkey::new_unique(),
+        bump: 255,
+        keys,
+    }
+}
+
+#[test]
+fn active_key_finds_the_version() {
+    let config = config(vec![key(1, VkStatus::Retired), key(2, VkStatus::Active)]);
+    assert_eq!(config.active_key(2).unwrap().version, 2);
+    assert_eq!(
+        error(config.active_key(1)),
+        ZordError::VerificationKeyRetired.into()
+    );
+    assert_eq!(
+        error(config.active_key(3)),
+        ZordError::VerificationKeyMissing.into()
+    );
+}
+
+#[test]
+fn retired_versions_cannot_be_registered_again() {
+    let mut config = config(vec![key(1, VkStatus::Active)]);
+    config.key_mut(1).unwrap().status = VkStatus::Retired;
+    assert_eq!(
+        config.add_key(key(1, VkStatus::Active)).unwrap_err(),
+        ZordError::VerificationKeyExists.into()
+    );
+
+    config.add_key(key(2, VkStatus::Active)).unwrap();
+    assert_eq!(config.active_key(2).unwrap().version, 2);
+    assert_eq!(
+        error(config.key_mut(3)),
+        ZordError::VerificationKeyMissing.into()
+    );
+}
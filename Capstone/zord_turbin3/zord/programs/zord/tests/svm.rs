@@ -0,0 +1,1338 @@
+// In-process tests of the SBF build of zord, no validator needed:
+//
+//   anchor build -p zord
+//   cargo test -p zord --test svm -- --ignored
+//
+// The legacy JSON test also needs a build with the feature enabled:
+//
+//   anchor build -p zord -- --features json-attestation
+//   cp target/deploy/zord.so target/deploy/zord_json_attestation.so
+//   cargo test -p zord --test svm --features json-attestation -- --ignored
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::error::InstructionError;
+use anchor_lang::AccountDeserialize;
+use common::zk::{self, field, Prover};
+use common::*;
+use litesvm::LiteSVM;
+use sha2::{Digest, Sha256};
+use solana_account::Account;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+use zord::circuit;
+use zord::claims::{ClaimSignal, CLAIM_AGE_OVER_18, CLAIM_MAX_AGE, CLAIM_RESIDENCY};
+use zord::errors::ZordError;
+use zord::state::claim_verifier::ClaimVerifier;
+use zord::instructions::initialize_verifier::GENESIS_VK_VERSION;
+use zord::state::attester_config::AttesterConfig;
+use zord::state::kyc_account::{
+    KycAccount, KycStatus, KYC_ACCOUNT_VERSION, KYC_RESERVED_LEN, REASON_COMPLIANCE,
+    REASON_FRAUD, REASON_NONE, REASON_USER_REQUEST, ZK_VERIFICATION_VALIDITY,
+};
+use zord::state::verifier_config::VerifierConfig;
+
+// KycAccount status discriminants
+const PENDING: u8 = 0;
+const VERIFIED: u8 = 1;
+
+struct Env {
+    svm: LiteSVM,
+    admin: Keypair,
+    user: Keypair,
+    attester: Keypair,
+    prover: Prover,
+}
+
+fn setup(program: &str) -> Env {
+    let program = deployed_program(program);
+    let mut svm = svm_with(&program);
+    let admin = funded_keypair(&mut svm);
+    let user = funded_keypair(&mut svm);
+    let attester = funded_keypair(&mut svm);
+    let prover = Prover::new(11);
+
+    set_attester_config(&mut svm, &admin.pubkey(), vec![attester.pubkey()]);
+    set_verifier_config(
+        &mut svm,
+        &admin.pubkey(),
+        vec![prover.vk_entry(TEST_VK_VERSION)],
+    );
+    Env {
+        svm,
+        admin,
+        user,
+        attester,
+        prover,
+    }
+}
+
+fn initialize(env: &mut Env) {
+    send(
+        &mut env.svm,
+        &[initialize_kyc_ix(&env.user.pubkey())],
+        &env.user,
+    )
+    .unwrap();
+}
+
+fn kyc_data(env: &Env) -> Vec<u8> {
+    let account = env.svm.get_account(&kyc_pda(&env.user.pubkey())).unwrap();
+    assert_eq!(account.owner, zord::ID);
+    account.data
+}
+
+// The on-chain layout spelled out field by field, independent of the Borsh
+// derive, so reordering or resizing a KycAccount field fails here.
+#[allow(clippy::too_many_arguments)]
+fn kyc_bytes(
+    user: &Pubkey,
+    status: u8,
+    attestation_hash: [u8; 32],
+    timestamp: i64,
+    expires_at: i64,
+    verified_vk_version: u32,
+    attester: &Pubkey,
+    revocation_reason: u8,
+) -> Vec<u8> {
+    let mut bytes = Sha256::digest(b"account:KycAccount")[..8].to_vec();
+    bytes.extend_from_slice(user.as_ref());
+    bytes.push(status);
+    bytes.extend_from_slice(&attestation_hash);
+    bytes.extend_from_slice(&timestamp.to_le_bytes());
+    bytes.extend_from_slice(&expires_at.to_le_bytes());
+    bytes.extend_from_slice(&verified_vk_version.to_le_bytes());
+    bytes.extend_from_slice(attester.as_ref());
+    bytes.push(revocation_reason);
+    bytes
+}
+
+// Those fields followed by the version byte, an empty claims bitmap, no Arcium
+// match, `document_commitment` and zeroed reserved space
+fn attested(mut bytes: Vec<u8>, document_commitment: [u8; 32]) -> Vec<u8> {
+    bytes.push(KYC_ACCOUNT_VERSION);
+    bytes.extend_from_slice(&0u64.to_le_bytes());
+    bytes.push(0);
+    bytes.extend_from_slice(&document_commitment);
+    bytes.extend_from_slice(&[0u8; KYC_RESERVED_LEN]);
+    bytes
+}
+
+// The same before any typed attestation, with no document commitment
+fn current(bytes: Vec<u8>) -> Vec<u8> {
+    attested(bytes, [0u8; 32])
+}
+
+// A KycAccount as zord wrote it before versioning
+fn v0_kyc_bytes(
+    user: &Pubkey,
+    is_verified: bool,
+    attestation_hash: [u8; 32],
+    timestamp: i64,
+) -> Vec<u8> {
+    let mut bytes = Sha256::digest(b"account:KycAccount")[..8].to_vec();
+    bytes.extend_from_slice(user.as_ref());
+    bytes.push(is_verified as u8);
+    bytes.extend_from_slice(&attestation_hash);
+    bytes.extend_from_slice(&timestamp.to_le_bytes());
+    bytes
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn initialize_kyc_writes_a_pending_record() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+
+    let expected = current(kyc_bytes(
+        &env.user.pubkey(),
+        PENDING,
+        [0u8; 32],
+        0,
+        0,
+        0,
+        &Pubkey::default(),
+        0,
+    ));
+    assert_eq!(kyc_data(&env), expected);
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn initialize_kyc_twice_fails() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+
+    let err = send(
+        &mut env.svm,
+        &[initialize_kyc_ix(&env.user.pubkey())],
+        &env.user,
+    )
+    .unwrap_err();
+    // SystemError::AccountAlreadyInUse from the create_account CPI
+    assert_eq!(
+        err.err,
+        TransactionError::InstructionError(0, InstructionError::Custom(0))
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn submit_attestation_stores_the_attestation() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+
+    let user = env.user.pubkey();
+    let attestation = attestation(&user, &env.attester.pubkey());
+    let hash = attestation.hash().unwrap();
+    let expires_at = attestation.expires_at;
+    let document_commitment = attestation.document_commitment;
+    send(
+        &mut env.svm,
+        &[
+            attester_signature(&env.attester, &user, &hash),
+            submit_attestation_ix(&user, attestation),
+        ],
+        &env.user,
+    )
+    .unwrap();
+
+    let expected = attested(
+        kyc_bytes(
+            &user,
+            VERIFIED,
+            hash,
+            NOW,
+            expires_at,
+            0,
+            &env.attester.pubkey(),
+            0,
+        ),
+        document_commitment,
+    );
+    assert_eq!(kyc_data(&env), expected);
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn verify_zk_accepts_a_valid_proof() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+
+    let user = env.user.pubkey();
+    let (proof, inputs) = env.prover.prove(&user);
+    send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, TEST_VK_VERSION, proof, inputs)],
+        &env.user,
+    )
+    .unwrap();
+
+    let expected = current(kyc_bytes(
+        &user,
+        VERIFIED,
+        [0u8; 32],
+        NOW,
+        NOW + ZK_VERIFICATION_VALIDITY,
+        TEST_VK_VERSION,
+        &Pubkey::default(),
+        0,
+    ));
+    assert_eq!(kyc_data(&env), expected);
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn verify_zk_rejects_a_tampered_proof() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+
+    let user = env.user.pubkey();
+    let (mut proof, inputs) = env.prover.prove(&user);
+    // C from an unrelated proof is still a valid curve point
+    let (other, _) = env.prover.prove(&Pubkey::new_unique());
+    proof[192..].copy_from_slice(&other[192..]);
+
+    let err = send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, TEST_VK_VERSION, proof, inputs)],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::VerificationFailed));
+
+    let expected = current(kyc_bytes(
+        &user,
+        PENDING,
+        [0u8; 32],
+        0,
+        0,
+        0,
+        &Pubkey::default(),
+        0,
+    ));
+    assert_eq!(kyc_data(&env), expected);
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn verify_zk_rejects_another_users_proof() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+
+    let (proof, inputs) = env.prover.prove(&Pubkey::new_unique());
+    let err = send(
+        &mut env.svm,
+        &[verify_zk_ix(
+            &env.user.pubkey(),
+            TEST_VK_VERSION,
+            proof,
+            inputs,
+        )],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::UserKeyMismatch));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn verify_zk_rejects_an_empty_proof() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+
+    let user = env.user.pubkey();
+    let (_, inputs) = env.prover.prove(&user);
+    let err = send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, TEST_VK_VERSION, vec![], inputs)],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::ProofDeserialize));
+}
+
+// a second funded wallet with its own pending KYC record
+fn other_wallet(env: &mut Env) -> Keypair {
+    let other = funded_keypair(&mut env.svm);
+    send(&mut env.svm, &[initialize_kyc_ix(&other.pubkey())], &other).unwrap();
+    other
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn verify_zk_holds_the_nullifier_for_one_wallet() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+
+    let user = env.user.pubkey();
+    let nullifier = zk::nullifier(&user);
+    let (proof, inputs) = env.prover.prove(&user);
+    let record = nullifier_pda(&inputs);
+    send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, TEST_VK_VERSION, proof, inputs)],
+        &env.user,
+    )
+    .unwrap();
+
+    let bump = Pubkey::find_program_address(&[b"nullifier", &nullifier], &zord::ID).1;
+    let mut expected = Sha256::digest(b"account:NullifierRecord")[..8].to_vec();
+    expected.extend_from_slice(&nullifier);
+    expected.extend_from_slice(user.as_ref());
+    expected.push(bump);
+    expected.extend_from_slice(&NOW.to_le_bytes());
+    assert_eq!(env.svm.get_account(&record).unwrap().data, expected);
+
+    // the same wallet may renew
+    let (proof, inputs) = env.prover.prove(&user);
+    send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, TEST_VK_VERSION, proof, inputs)],
+        &env.user,
+    )
+    .unwrap();
+
+    // another wallet proving the same identity may not
+    let other = other_wallet(&mut env);
+    let (proof, inputs) = env.prover.prove_identity(&other.pubkey(), &nullifier);
+    let err = send(
+        &mut env.svm,
+        &[verify_zk_ix(
+            &other.pubkey(),
+            TEST_VK_VERSION,
+            proof,
+            inputs,
+        )],
+        &other,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::NullifierAlreadyUsed));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn release_nullifier_moves_the_identity_to_a_new_wallet() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+
+    let user = env.user.pubkey();
+    let nullifier = zk::nullifier(&user);
+    let (proof, inputs) = env.prover.prove(&user);
+    let record = nullifier_pda(&inputs);
+    send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, TEST_VK_VERSION, proof, inputs)],
+        &env.user,
+    )
+    .unwrap();
+
+    let record_rent = env.svm.get_account(&record).unwrap().lamports;
+    let balance = env.svm.get_balance(&user).unwrap();
+    send(
+        &mut env.svm,
+        &[release_nullifier_ix(&env.admin.pubkey(), &user, &nullifier)],
+        &env.admin,
+    )
+    .unwrap();
+
+    // the old wallet is revoked and refunded, the record is gone
+    assert!(env.svm.get_account(&record).is_none_or(|a| a.lamports == 0));
+    assert_eq!(env.svm.get_balance(&user).unwrap(), balance + record_rent);
+    let kyc = KycAccount::try_deserialize(&mut &kyc_data(&env)[..]).unwrap();
+    assert_eq!(kyc.status, KycStatus::Revoked);
+    assert_eq!(kyc.revocation_reason, REASON_USER_REQUEST);
+
+    let other = other_wallet(&mut env);
+    let (proof, inputs) = env.prover.prove_identity(&other.pubkey(), &nullifier);
+    send(
+        &mut env.svm,
+        &[verify_zk_ix(
+            &other.pubkey(),
+            TEST_VK_VERSION,
+            proof,
+            inputs,
+        )],
+        &other,
+    )
+    .unwrap();
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn release_nullifier_requires_the_admin() {
+    let mut env = setup("zord.so");
+    verify(&mut env);
+
+    let user = env.user.pubkey();
+    let err = send(
+        &mut env.svm,
+        &[release_nullifier_ix(
+            &env.attester.pubkey(),
+            &user,
+            &zk::nullifier(&user),
+        )],
+        &env.attester,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::Unauthorized));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn record_arcium_match_requires_the_arcium_authority() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+
+    // the user signs in place of the Arcium program's PDA
+    let user = env.user.pubkey();
+    let err = send(
+        &mut env.svm,
+        &[record_arcium_match_ix(&user, &user, true)],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(anchor_lang::error::ErrorCode::ConstraintSeeds.into())
+        )
+    );
+
+    let kyc = KycAccount::try_deserialize(&mut &kyc_data(&env)[..]).unwrap();
+    assert!(!kyc.arcium_match);
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn migrate_kyc_upgrades_a_v0_account() {
+    let mut env = setup("zord.so");
+    let user = env.user.pubkey();
+    let v0 = v0_kyc_bytes(&user, true, [9u8; 32], NOW - 10);
+    assert_eq!(v0.len(), 8 + KycAccount::V0_SIZE);
+
+    let v0_rent = env.svm.minimum_balance_for_rent_exemption(v0.len());
+    let account = Account {
+        lamports: v0_rent,
+        data: v0,
+        owner: zord::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    env.svm.set_account(kyc_pda(&user), account).unwrap();
+
+    let balance = env.svm.get_balance(&user).unwrap();
+    send(&mut env.svm, &[migrate_kyc_ix(&user)], &env.user).unwrap();
+
+    let migrated = env.svm.get_account(&kyc_pda(&user)).unwrap();
+    let new_rent = env
+        .svm
+        .minimum_balance_for_rent_exemption(8 + KycAccount::SIZE);
+    // is_verified becomes Verified, with the validity verify_zk grants
+    let expected = current(kyc_bytes(
+        &user,
+        VERIFIED,
+        [9u8; 32],
+        NOW - 10,
+        NOW - 10 + ZK_VERIFICATION_VALIDITY,
+        0,
+        &Pubkey::default(),
+        0,
+    ));
+    assert_eq!(migrated.data, expected);
+    assert_eq!(migrated.lamports, new_rent);
+
+    let kyc = KycAccount::try_deserialize(&mut &migrated.data[..]).unwrap();
+    assert_eq!(kyc.user, user);
+    assert!(kyc.is_verified(NOW));
+    assert_eq!(kyc.version, KYC_ACCOUNT_VERSION);
+
+    // rent difference plus the signature fee
+    let paid = balance - env.svm.get_balance(&user).unwrap();
+    assert_eq!(paid, new_rent - v0_rent + 5000);
+
+    let err = send(&mut env.svm, &[migrate_kyc_ix(&user)], &env.user).unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::KycAlreadyMigrated));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn migrate_kyc_keeps_an_unverified_v0_account_pending() {
+    let mut env = setup("zord.so");
+    let user = env.user.pubkey();
+    let v0 = v0_kyc_bytes(&user, false, [0u8; 32], 0);
+    let account = Account {
+        lamports: env.svm.minimum_balance_for_rent_exemption(v0.len()),
+        data: v0,
+        owner: zord::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    env.svm.set_account(kyc_pda(&user), account).unwrap();
+
+    send(&mut env.svm, &[migrate_kyc_ix(&user)], &env.user).unwrap();
+
+    let expected = current(kyc_bytes(
+        &user,
+        PENDING,
+        [0u8; 32],
+        0,
+        0,
+        0,
+        &Pubkey::default(),
+        0,
+    ));
+    assert_eq!(kyc_data(&env), expected);
+}
+
+// verify_zk for env.user, so claim checks see a verified record
+fn verify(env: &mut Env) {
+    initialize(env);
+    let user = env.user.pubkey();
+    let (proof, inputs) = env.prover.prove(&user);
+    send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, TEST_VK_VERSION, proof, inputs)],
+        &env.user,
+    )
+    .unwrap();
+}
+
+// over-18 circuit: (valid, user_key_commitment, nullifier, minimum age,
+// evaluated at)
+fn age_layout() -> Vec<ClaimSignal> {
+    vec![
+        ClaimSignal::Valid,
+        ClaimSignal::UserKeyCommitment,
+        ClaimSignal::Nullifier,
+        ClaimSignal::Constant(field(18)),
+        ClaimSignal::Timestamp,
+    ]
+}
+
+fn age_claim(env: &mut Env) -> Prover {
+    let prover = Prover::with_inputs(21, 5);
+    let layout = age_layout();
+    let admin = env.admin.pubkey();
+    send(
+        &mut env.svm,
+        &[register_claim_ix(
+            &admin,
+            CLAIM_AGE_OVER_18,
+            layout,
+            prover.vk_input(),
+        )],
+        &env.admin,
+    )
+    .unwrap();
+    prover
+}
+
+fn age_inputs(user: &Pubkey, minimum_age: u64, evaluated_at: i64) -> Vec<[u8; 32]> {
+    vec![
+        field(1),
+        circuit::user_key_commitment(user),
+        zk::nullifier(user),
+        field(minimum_age),
+        field(evaluated_at as u64),
+    ]
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn verify_claim_sets_only_that_claim() {
+    let mut env = setup("zord.so");
+    verify(&mut env);
+    let mut prover = age_claim(&mut env);
+
+    let user = env.user.pubkey();
+    let inputs = age_inputs(&user, 18, NOW - 60);
+    let proof = prover.prove_inputs(&inputs);
+    send(
+        &mut env.svm,
+        &[verify_claim_ix(
+            &user,
+            CLAIM_AGE_OVER_18,
+            &zk::nullifier(&user),
+            proof,
+            inputs,
+        )],
+        &env.user,
+    )
+    .unwrap();
+
+    let kyc = KycAccount::try_deserialize(&mut &kyc_data(&env)[..]).unwrap();
+    assert_eq!(kyc.claims, 1 << CLAIM_AGE_OVER_18);
+
+    send(
+        &mut env.svm,
+        &[check_claim_ix(&user, CLAIM_AGE_OVER_18)],
+        &env.user,
+    )
+    .unwrap();
+    let err = send(
+        &mut env.svm,
+        &[check_claim_ix(&user, CLAIM_RESIDENCY)],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::ClaimNotVerified));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn verify_claim_rejects_a_different_constant() {
+    let mut env = setup("zord.so");
+    verify(&mut env);
+    let mut prover = age_claim(&mut env);
+
+    // a valid proof, but of "over 16"
+    let user = env.user.pubkey();
+    let inputs = age_inputs(&user, 16, NOW - 60);
+    let proof = prover.prove_inputs(&inputs);
+    let err = send(
+        &mut env.svm,
+        &[verify_claim_ix(
+            &user,
+            CLAIM_AGE_OVER_18,
+            &zk::nullifier(&user),
+            proof,
+            inputs,
+        )],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::ClaimInputMismatch));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn verify_claim_rejects_a_stale_evaluation() {
+    let mut env = setup("zord.so");
+    verify(&mut env);
+    let mut prover = age_claim(&mut env);
+
+    let user = env.user.pubkey();
+    let inputs = age_inputs(&user, 18, NOW - CLAIM_MAX_AGE - 1);
+    let proof = prover.prove_inputs(&inputs);
+    let err = send(
+        &mut env.svm,
+        &[verify_claim_ix(
+            &user,
+            CLAIM_AGE_OVER_18,
+            &zk::nullifier(&user),
+            proof,
+            inputs,
+        )],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::StaleClaimTimestamp));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn register_claim_requires_a_user_binding() {
+    let mut env = setup("zord.so");
+    let prover = Prover::with_inputs(22, 2);
+    let layout = vec![ClaimSignal::Valid, ClaimSignal::Constant(field(356))];
+
+    let admin = env.admin.pubkey();
+    let err = send(
+        &mut env.svm,
+        &[register_claim_ix(
+            &admin,
+            CLAIM_RESIDENCY,
+            layout,
+            prover.vk_input(),
+        )],
+        &env.admin,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::InvalidClaimLayout));
+
+    // bound to the wallet, but not to the verified identity
+    let layout = vec![ClaimSignal::Valid, ClaimSignal::UserKeyCommitment];
+    let err = send(
+        &mut env.svm,
+        &[register_claim_ix(
+            &admin,
+            CLAIM_RESIDENCY,
+            layout,
+            prover.vk_input(),
+        )],
+        &env.admin,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::InvalidClaimLayout));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn verify_claim_rejects_another_identity() {
+    let mut env = setup("zord.so");
+    verify(&mut env);
+    let mut prover = age_claim(&mut env);
+
+    // a valid over-18 proof, but from someone else's document
+    let user = env.user.pubkey();
+    let mut inputs = age_inputs(&user, 18, NOW - 60);
+    inputs[2] = zk::nullifier(&Pubkey::new_unique());
+    let proof = prover.prove_inputs(&inputs);
+    let err = send(
+        &mut env.svm,
+        &[verify_claim_ix(
+            &user,
+            CLAIM_AGE_OVER_18,
+            &zk::nullifier(&user),
+            proof,
+            inputs,
+        )],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::ClaimIdentityMismatch));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn verify_claim_needs_the_wallets_own_nullifier_record() {
+    let mut env = setup("zord.so");
+    verify(&mut env);
+    let mut prover = age_claim(&mut env);
+
+    // another wallet verified with its own document
+    let other = other_wallet(&mut env);
+    let (proof, inputs) = env.prover.prove(&other.pubkey());
+    send(
+        &mut env.svm,
+        &[verify_zk_ix(
+            &other.pubkey(),
+            TEST_VK_VERSION,
+            proof,
+            inputs,
+        )],
+        &other,
+    )
+    .unwrap();
+
+    // claiming that document's identity through its record fails
+    let user = env.user.pubkey();
+    let mut inputs = age_inputs(&user, 18, NOW - 60);
+    inputs[2] = zk::nullifier(&other.pubkey());
+    let proof = prover.prove_inputs(&inputs);
+    let err = send(
+        &mut env.svm,
+        &[verify_claim_ix(
+            &user,
+            CLAIM_AGE_OVER_18,
+            &zk::nullifier(&other.pubkey()),
+            proof,
+            inputs,
+        )],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::ClaimIdentityMismatch));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn retire_and_rotate_claim() {
+    let mut env = setup("zord.so");
+    verify(&mut env);
+    let mut prover = age_claim(&mut env);
+    let admin = env.admin.pubkey();
+    let user = env.user.pubkey();
+
+    send(
+        &mut env.svm,
+        &[retire_claim_ix(&admin, CLAIM_AGE_OVER_18)],
+        &env.admin,
+    )
+    .unwrap();
+    let inputs = age_inputs(&user, 18, NOW - 60);
+    let proof = prover.prove_inputs(&inputs);
+    let err = send(
+        &mut env.svm,
+        &[verify_claim_ix(
+            &user,
+            CLAIM_AGE_OVER_18,
+            &zk::nullifier(&user),
+            proof,
+            inputs.clone(),
+        )],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::VerificationKeyRetired));
+
+    // the next key takes over, proofs for the old one stay rejected
+    let mut rotated = Prover::with_inputs(23, 5);
+    send(
+        &mut env.svm,
+        &[rotate_claim_ix(
+            &admin,
+            CLAIM_AGE_OVER_18,
+            age_layout(),
+            rotated.vk_input(),
+        )],
+        &env.admin,
+    )
+    .unwrap();
+    let claim = env.svm.get_account(&claim_pda(CLAIM_AGE_OVER_18)).unwrap();
+    let claim = ClaimVerifier::try_deserialize(&mut &claim.data[..]).unwrap();
+    assert_eq!(claim.key.version, 2);
+
+    let stale = prover.prove_inputs(&inputs);
+    let err = send(
+        &mut env.svm,
+        &[verify_claim_ix(
+            &user,
+            CLAIM_AGE_OVER_18,
+            &zk::nullifier(&user),
+            stale,
+            inputs.clone(),
+        )],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::VerificationFailed));
+
+    let proof = rotated.prove_inputs(&inputs);
+    send(
+        &mut env.svm,
+        &[verify_claim_ix(
+            &user,
+            CLAIM_AGE_OVER_18,
+            &zk::nullifier(&user),
+            proof,
+            inputs,
+        )],
+        &env.user,
+    )
+    .unwrap();
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn rotate_and_retire_claim_require_the_admin() {
+    let mut env = setup("zord.so");
+    let prover = age_claim(&mut env);
+    let user = env.user.pubkey();
+
+    let err = send(
+        &mut env.svm,
+        &[retire_claim_ix(&user, CLAIM_AGE_OVER_18)],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::Unauthorized));
+
+    let err = send(
+        &mut env.svm,
+        &[rotate_claim_ix(
+            &user,
+            CLAIM_AGE_OVER_18,
+            age_layout(),
+            prover.vk_input(),
+        )],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::Unauthorized));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn releasing_the_identity_clears_the_claims() {
+    let mut env = setup("zord.so");
+    verify(&mut env);
+    let mut prover = age_claim(&mut env);
+
+    let user = env.user.pubkey();
+    let inputs = age_inputs(&user, 18, NOW - 60);
+    let proof = prover.prove_inputs(&inputs);
+    send(
+        &mut env.svm,
+        &[verify_claim_ix(
+            &user,
+            CLAIM_AGE_OVER_18,
+            &zk::nullifier(&user),
+            proof,
+            inputs,
+        )],
+        &env.user,
+    )
+    .unwrap();
+
+    let admin = env.admin.pubkey();
+    send(
+        &mut env.svm,
+        &[release_nullifier_ix(&admin, &user, &zk::nullifier(&user))],
+        &env.admin,
+    )
+    .unwrap();
+
+    let kyc = KycAccount::try_deserialize(&mut &kyc_data(&env)[..]).unwrap();
+    assert_eq!(kyc.status, KycStatus::Revoked);
+    assert_eq!(kyc.claims, 0);
+}
+
+// env.user verified through an attestation from env.attester
+fn attest(env: &mut Env, attestation: zord::attestation::Attestation) {
+    let user = env.user.pubkey();
+    let hash = attestation.hash().unwrap();
+    send(
+        &mut env.svm,
+        &[
+            attester_signature(&env.attester, &user, &hash),
+            submit_attestation_ix(&user, attestation),
+        ],
+        &env.user,
+    )
+    .unwrap();
+}
+
+fn kyc(env: &Env) -> KycAccount {
+    KycAccount::try_deserialize(&mut &kyc_data(env)[..]).unwrap()
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn submit_attestation_needs_an_allowed_signer() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+    let user = env.user.pubkey();
+    let attestation = attestation(&user, &env.attester.pubkey());
+    let hash = attestation.hash().unwrap();
+
+    let err = send(
+        &mut env.svm,
+        &[submit_attestation_ix(&user, attestation.clone())],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::MissingAttesterSignature));
+
+    // the instruction right before has to be the Ed25519 precompile
+    let err = send(
+        &mut env.svm,
+        &[
+            attester_signature(&env.attester, &user, &hash),
+            ComputeBudgetInstruction::set_compute_unit_limit(400_000),
+            submit_attestation_ix(&user, attestation.clone()),
+        ],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(2, ZordError::MissingAttesterSignature));
+
+    let stranger = Keypair::new();
+    let forged = zord::attestation::Attestation {
+        issuer: stranger.pubkey(),
+        ..attestation.clone()
+    };
+    let err = send(
+        &mut env.svm,
+        &[
+            attester_signature(&stranger, &user, &forged.hash().unwrap()),
+            submit_attestation_ix(&user, forged),
+        ],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(1, ZordError::UnknownAttester));
+
+    // an allowed attester, but over another attestation
+    let err = send(
+        &mut env.svm,
+        &[
+            attester_signature(&env.attester, &user, &[7u8; 32]),
+            submit_attestation_ix(&user, attestation),
+        ],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(1, ZordError::InvalidAttesterSignature));
+
+    assert_eq!(kyc(&env).status, KycStatus::Pending);
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn suspend_kyc_holds_until_a_fresh_attestation() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+    let user = env.user.pubkey();
+    let first = attestation(&user, &env.attester.pubkey());
+    attest(&mut env, first.clone());
+
+    let err = send(
+        &mut env.svm,
+        &[suspend_kyc_ix(&user, &user, REASON_FRAUD)],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::Unauthorized));
+
+    let attester = env.attester.pubkey();
+    send(
+        &mut env.svm,
+        &[suspend_kyc_ix(&attester, &user, REASON_FRAUD)],
+        &env.attester,
+    )
+    .unwrap();
+    let kyc_record = kyc(&env);
+    assert_eq!(kyc_record.status, KycStatus::Suspended);
+    assert_eq!(kyc_record.revocation_reason, REASON_FRAUD);
+    assert_eq!(kyc_record.timestamp, NOW);
+
+    // neither the attestation it was verified with nor a proof lifts it
+    let hash = first.hash().unwrap();
+    let err = send(
+        &mut env.svm,
+        &[
+            attester_signature(&env.attester, &user, &hash),
+            submit_attestation_ix(&user, first.clone()),
+        ],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(1, ZordError::StaleAttestation));
+    let (proof, inputs) = env.prover.prove(&user);
+    let err = send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, TEST_VK_VERSION, proof, inputs)],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::KycSuspended));
+
+    set_time(&mut env.svm, NOW + 60);
+    attest(
+        &mut env,
+        zord::attestation::Attestation {
+            issued_at: NOW + 1,
+            ..first
+        },
+    );
+    let kyc_record = kyc(&env);
+    assert_eq!(kyc_record.status, KycStatus::Verified);
+    assert_eq!(kyc_record.revocation_reason, REASON_NONE);
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn revoke_kyc_is_terminal() {
+    let mut env = setup("zord.so");
+    verify(&mut env);
+    let user = env.user.pubkey();
+    let admin = env.admin.pubkey();
+
+    let err = send(
+        &mut env.svm,
+        &[revoke_kyc_ix(&user, &user, REASON_COMPLIANCE)],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::Unauthorized));
+
+    send(
+        &mut env.svm,
+        &[revoke_kyc_ix(&admin, &user, REASON_COMPLIANCE)],
+        &env.admin,
+    )
+    .unwrap();
+    let kyc_record = kyc(&env);
+    assert_eq!(kyc_record.status, KycStatus::Revoked);
+    assert_eq!(kyc_record.revocation_reason, REASON_COMPLIANCE);
+
+    for ix in [
+        revoke_kyc_ix(&admin, &user, REASON_FRAUD),
+        suspend_kyc_ix(&admin, &user, REASON_FRAUD),
+    ] {
+        let err = send(&mut env.svm, &[ix], &env.admin).unwrap_err();
+        assert_eq!(err.err, zord_error(0, ZordError::KycRevoked));
+    }
+
+    let fresh = zord::attestation::Attestation {
+        issued_at: NOW,
+        ..attestation(&user, &env.attester.pubkey())
+    };
+    let hash = fresh.hash().unwrap();
+    let err = send(
+        &mut env.svm,
+        &[
+            attester_signature(&env.attester, &user, &hash),
+            submit_attestation_ix(&user, fresh),
+        ],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(1, ZordError::KycRevoked));
+    assert_eq!(kyc(&env).revocation_reason, REASON_COMPLIANCE);
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn expire_kyc_persists_a_lapsed_verification() {
+    let mut env = setup("zord.so");
+    verify(&mut env);
+    let user = env.user.pubkey();
+
+    // permissionless, any payer may send it
+    set_time(&mut env.svm, NOW + ZK_VERIFICATION_VALIDITY - 1);
+    let err = send(&mut env.svm, &[expire_kyc_ix(&user)], &env.attester).unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::KycNotExpired));
+    assert_eq!(kyc(&env).status, KycStatus::Verified);
+
+    set_time(&mut env.svm, NOW + ZK_VERIFICATION_VALIDITY);
+    send(&mut env.svm, &[expire_kyc_ix(&user)], &env.attester).unwrap();
+    assert_eq!(kyc(&env).status, KycStatus::Expired);
+
+    let err = send(&mut env.svm, &[expire_kyc_ix(&user)], &env.attester).unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::KycNotExpired));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn register_rotate_and_retire_vk() {
+    let mut env = setup("zord.so");
+    initialize(&mut env);
+    let admin = env.admin.pubkey();
+    let user = env.user.pubkey();
+    let mut second = Prover::new(12);
+    let mut third = Prover::new(13);
+
+    let err = send(
+        &mut env.svm,
+        &[register_vk_ix(&user, 2, second.vk_input())],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::Unauthorized));
+
+    send(
+        &mut env.svm,
+        &[register_vk_ix(&admin, 2, second.vk_input())],
+        &env.admin,
+    )
+    .unwrap();
+    let err = send(
+        &mut env.svm,
+        &[register_vk_ix(&admin, 2, third.vk_input())],
+        &env.admin,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::VerificationKeyExists));
+
+    let (proof, inputs) = second.prove(&user);
+    send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, 2, proof, inputs)],
+        &env.user,
+    )
+    .unwrap();
+
+    // rotating registers the new key and retires the old one
+    send(
+        &mut env.svm,
+        &[rotate_vk_ix(&admin, 2, 3, third.vk_input())],
+        &env.admin,
+    )
+    .unwrap();
+    let (proof, inputs) = second.prove(&user);
+    let err = send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, 2, proof, inputs)],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::VerificationKeyRetired));
+    let (proof, inputs) = third.prove(&user);
+    send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, 3, proof, inputs)],
+        &env.user,
+    )
+    .unwrap();
+
+    let err = send(
+        &mut env.svm,
+        &[rotate_vk_ix(&admin, 2, 4, second.vk_input())],
+        &env.admin,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::VerificationKeyRetired));
+
+    let err = send(&mut env.svm, &[retire_vk_ix(&user, 3)], &env.user).unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::Unauthorized));
+    send(&mut env.svm, &[retire_vk_ix(&admin, 3)], &env.admin).unwrap();
+    let err = send(&mut env.svm, &[retire_vk_ix(&admin, 3)], &env.admin).unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::VerificationKeyRetired));
+    let err = send(&mut env.svm, &[retire_vk_ix(&admin, 9)], &env.admin).unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::VerificationKeyMissing));
+
+    let (proof, inputs) = third.prove(&user);
+    let err = send(
+        &mut env.svm,
+        &[verify_zk_ix(&user, 3, proof, inputs)],
+        &env.user,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, zord_error(0, ZordError::VerificationKeyRetired));
+}
+
+#[test]
+#[ignore = "needs target/deploy/zord.so from `anchor build`"]
+fn singleton_configs_need_the_upgrade_authority() {
+    let authority = Keypair::new();
+    let mut svm = upgradeable_svm_with(&deployed_program("zord.so"), &authority.pubkey());
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    let stranger = funded_keypair(&mut svm);
+
+    for ix in [
+        initialize_verifier_ix(&stranger.pubkey()),
+        initialize_attesters_ix(&stranger.pubkey()),
+    ] {
+        let err = send(&mut svm, &[ix], &stranger).unwrap_err();
+        assert_eq!(err.err, zord_error(0, ZordError::NotUpgradeAuthority));
+    }
+
+    let admin = authority.pubkey();
+    send(
+        &mut svm,
+        &[initialize_verifier_ix(&admin), initialize_attesters_ix(&admin)],
+        &authority,
+    )
+    .unwrap();
+    let account = svm.get_account(&vk_pda().0).unwrap();
+    let verifier = VerifierConfig::try_deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(verifier.admin, admin);
+    assert!(verifier.active_key(GENESIS_VK_VERSION).is_ok());
+    let account = svm.get_account(&attesters_pda().0).unwrap();
+    let attesters = AttesterConfig::try_deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(attesters.admin, admin);
+    assert!(attesters.attesters.is_empty());
+}
+
+#[cfg(feature = "json-attestation")]
+#[allow(clippy::result_large_err)]
+fn submit_attestation_json(
+    env: &mut Env,
+    attestation_json: &str,
+) -> Result<litesvm::types::TransactionMetadata, litesvm::types::FailedTransactionMetadata> {
+    use anchor_lang::solana_program::instruction::Instruction;
+    use anchor_lang::{InstructionData, ToAccountMetas};
+
+    let user = env.user.pubkey();
+    let hash: [u8; 32] = Sha256::digest(attestation_json.as_bytes()).into();
+    // the legacy instruction under its original name and argument
+    let submit = Instruction {
+        program_id: zord::ID,
+        accounts: zord::accounts::SubmitAttestationJson {
+            user,
+            kyc_account: kyc_pda(&user),
+            attester_config: attesters_pda().0,
+            instructions: solana_sdk_ids::sysvar::instructions::ID,
+        }
+        .to_account_metas(None),
+        data: zord::instruction::SubmitAttestation {
+            attestation_json: attestation_json.to_string(),
+        }
+        .data(),
+    };
+
+    send(
+        &mut env.svm,
+        &[attester_signature(&env.attester, &user, &hash), submit],
+        &env.user,
+    )
+}
+
+#[cfg(feature = "json-attestation")]
+#[test]
+#[ignore = "needs target/deploy/zord_json_attestation.so, see the top of this file"]
+fn submit_attestation_json_rejects_malformed_json() {
+    let mut env = setup("zord_json_attestation.so");
+    initialize(&mut env);
+
+    let err = submit_attestation_json(&mut env, "{\"is_valid\": tru").unwrap_err();
+    assert_eq!(err.err, zord_error(1, ZordError::InvalidJson));
+}
+
+#[cfg(feature = "json-attestation")]
+#[test]
+#[ignore = "needs target/deploy/zord_json_attestation.so, see the top of this file"]
+fn submit_attestation_json_rejects_invalid_attestation() {
+    let mut env = setup("zord_json_attestation.so");
+    initialize(&mut env);
+
+    let err = submit_attestation_json(&mut env, "{\"is_valid\": false}").unwrap_err();
+    assert_eq!(err.err, zord_error(1, ZordError::AttestationNotValid));
+}
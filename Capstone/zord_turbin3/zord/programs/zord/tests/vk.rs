@@ -0,0 +1,84 @@
+// Guards the build.rs-generated verification key against the snarkjs artifacts
+// and the program's public-signal layout: if the circuit is rotated, these fail
+// until the key is regenerated.
+
+use num_bigint::BigUint;
+use serde_json::Value;
+use zord::circuit;
+use zord::groth16::{self, Groth16Proof};
+use zord::vk::*;
+
+const ZK_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../zk/passport_pan_zk");
+
+fn load(name: &str) -> Value {
+    let path = format!("{}/{}", ZK_DIR, name);
+    serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+fn field(value: &Value) -> Vec<u8> {
+    let n = BigUint::parse_bytes(value.as_str().unwrap().as_bytes(), 10).unwrap();
+    let bytes = n.to_bytes_be();
+    let mut padded = vec![0u8; 32 - bytes.len()];
+    padded.extend_from_slice(&bytes);
+    padded
+}
+
+fn g1(point: &Value) -> Vec<u8> {
+    [field(&point[0]), field(&point[1])].concat()
+}
+
+fn g2(point: &Value) -> Vec<u8> {
+    [
+        field(&point[0][1]),
+        field(&point[0][0]),
+        field(&point[1][1]),
+        field(&point[1][0]),
+    ]
+    .concat()
+}
+
+#[test]
+fn generated_key_matches_verification_key_json() {
+    let vk = load("verification_key.json");
+
+    assert_eq!(NR_PUBINPUTS as u64, vk["nPublic"].as_u64().unwrap());
+    assert_eq!(VK_ALPHA_G1.to_vec(), g1(&vk["vk_alpha_1"]));
+    assert_eq!(VK_BETA_G2.to_vec(), g2(&vk["vk_beta_2"]));
+    assert_eq!(VK_GAMMA_G2.to_vec(), g2(&vk["vk_gamma_2"]));
+    assert_eq!(VK_DELTA_G2.to_vec(), g2(&vk["vk_delta_2"]));
+
+    let ic = vk["IC"].as_array().unwrap();
+    assert_eq!(VK_IC.len(), ic.len());
+    for (generated, expected) in VK_IC.iter().zip(ic) {
+        assert_eq!(generated.to_vec(), g1(expected));
+    }
+}
+
+#[test]
+fn generated_key_verifies_reference_proof() {
+    let proof = load("proof.json");
+    let public: Vec<[u8; 32]> = load("public.json")
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| field(s).try_into().unwrap())
+        .collect();
+
+    let proof_bytes = [g1(&proof["pi_a"]), g2(&proof["pi_b"]), g1(&proof["pi_c"])].concat();
+    let proof = Groth16Proof::try_from_bytes(&proof_bytes).unwrap();
+
+    groth16::verify(&proof, &public, &VERIFYING_KEY).unwrap();
+}
+
+#[test]
+fn artifacts_have_the_programs_public_signals() {
+    let vk = load("verification_key.json");
+    assert_eq!(
+        vk["nPublic"].as_u64().unwrap(),
+        circuit::NR_PUBLIC_SIGNALS as u64,
+        "verification_key.json is stale, rebuild the circuit artifacts"
+    );
+
+    let public = load("public.json");
+    assert_eq!(public.as_array().unwrap().len(), circuit::NR_PUBLIC_SIGNALS);
+}
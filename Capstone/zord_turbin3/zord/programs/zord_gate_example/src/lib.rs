@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use zord::program::Zord;
+
+declare_id!("69cDduW4xT44TxxXmJpP8a9beLnokoZDTS8ZnMdVvgAR");
+
+// Minimal consumer of zord's KYC gate: deposits are only accepted from users
+// whose KYC record is verified and unexpired.
+#[program]
+pub mod zord_gate_example {
+    use super::*;
+
+    pub fn gated_deposit(ctx: Context<GatedDeposit>, amount: u64) -> Result<()> {
+        // fails the whole transaction with zord's error if the user is not verified
+        zord::cpi::check_kyc(CpiContext::new(
+            ctx.accounts.zord_program.to_account_info(),
+            zord::cpi::accounts::CheckKyc {
+                user: ctx.accounts.user.to_account_info(),
+                kyc_account: ctx.accounts.kyc_account.to_account_info(),
+            },
+        ))?;
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct GatedDeposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: validated by zord's `check_kyc`
+    pub kyc_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub zord_program: Program<'info, Zord>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,193 @@
+// Runs zord and this example program side by side in LiteSVM. The tests are
+// ignored by default since they need the SBF builds in target/deploy:
+//
+//   anchor build
+//   cargo test -p zord_gate_example -- --ignored
+
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::solana_program::instruction::{error::InstructionError, Instruction};
+use anchor_lang::{system_program, AccountSerialize, InstructionData, ToAccountMetas};
+use litesvm::LiteSVM;
+use solana_account::Account;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+use zord::errors::ZordError;
+use zord::state::kyc_account::{
+    KycAccount, KycStatus, KYC_ACCOUNT_VERSION, KYC_RESERVED_LEN, REASON_FRAUD, REASON_NONE,
+};
+
+const DEPLOY_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy");
+const NOW: i64 = 1_700_000_000;
+const DEPOSIT: u64 = 1_000_000;
+
+fn setup() -> (LiteSVM, Keypair) {
+    let zord_so = format!("{}/zord.so", DEPLOY_DIR);
+    let gate_so = format!("{}/zord_gate_example.so", DEPLOY_DIR);
+    for program in [&zord_so, &gate_so] {
+        assert!(
+            std::path::Path::new(program).exists(),
+            "{program} is missing, run `anchor build` first"
+        );
+    }
+
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(zord::ID, zord_so).unwrap();
+    svm.add_program_from_file(zord_gate_example::ID, gate_so).unwrap();
+
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = NOW;
+    svm.set_sysvar(&clock);
+
+    let user = Keypair::new();
+    svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+    (svm, user)
+}
+
+fn kyc_pda(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"kyc", user.as_ref()], &zord::ID).0
+}
+
+fn vault_pda(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", user.as_ref()], &zord_gate_example::ID).0
+}
+
+// writes a zord-owned KycAccount straight into the bank
+fn set_kyc(svm: &mut LiteSVM, user: &Pubkey, status: KycStatus, expires_at: i64, reason: u8) {
+    let kyc = KycAccount {
+        user: *user,
+        status,
+        attestation_hash: [7u8; 32],
+        timestamp: NOW - 60,
+        expires_at,
+        verified_vk_version: 1,
+        attester: Pubkey::new_unique(),
+        revocation_reason: reason,
+        version: KYC_ACCOUNT_VERSION,
+        claims: 0,
+        arcium_match: false,
+        document_commitment: [0u8; 32],
+        reserved: [0u8; KYC_RESERVED_LEN],
+    };
+    let mut data = Vec::with_capacity(8 + KycAccount::SIZE);
+    kyc.try_serialize(&mut data).unwrap();
+
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: zord::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(kyc_pda(user), account).unwrap();
+}
+
+fn gated_deposit(svm: &mut LiteSVM, user: &Keypair, kyc_account: Pubkey) -> Result<(), TransactionError> {
+    let ix = Instruction {
+        program_id: zord_gate_example::ID,
+        accounts: zord_gate_example::accounts::GatedDeposit {
+            user: user.pubkey(),
+            kyc_account,
+            vault: vault_pda(&user.pubkey()),
+            zord_program: zord::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: zord_gate_example::instruction::GatedDeposit { amount: DEPOSIT }.data(),
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user.pubkey()),
+        &[user],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).map(|_| ()).map_err(|failed| failed.err)
+}
+
+fn assert_zord_error(result: Result<(), TransactionError>, expected: ZordError) {
+    assert_eq!(
+        result.unwrap_err(),
+        TransactionError::InstructionError(0, InstructionError::Custom(expected.into()))
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy from `anchor build`"]
+fn verified_user_can_deposit() {
+    let (mut svm, user) = setup();
+    set_kyc(&mut svm, &user.pubkey(), KycStatus::Verified, NOW + 3600, REASON_NONE);
+
+    gated_deposit(&mut svm, &user, kyc_pda(&user.pubkey())).unwrap();
+    assert_eq!(svm.get_balance(&vault_pda(&user.pubkey())), Some(DEPOSIT));
+}
+
+#[test]
+#[ignore = "needs target/deploy from `anchor build`"]
+fn pending_user_is_rejected() {
+    let (mut svm, user) = setup();
+    set_kyc(&mut svm, &user.pubkey(), KycStatus::Pending, 0, REASON_NONE);
+
+    assert_zord_error(
+        gated_deposit(&mut svm, &user, kyc_pda(&user.pubkey())),
+        ZordError::KycNotVerified,
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy from `anchor build`"]
+fn expired_verification_is_rejected() {
+    let (mut svm, user) = setup();
+    set_kyc(&mut svm, &user.pubkey(), KycStatus::Verified, NOW + 3600, REASON_NONE);
+
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = NOW + 3600;
+    svm.set_sysvar(&clock);
+
+    assert_zord_error(
+        gated_deposit(&mut svm, &user, kyc_pda(&user.pubkey())),
+        ZordError::KycExpired,
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy from `anchor build`"]
+fn revoked_user_is_rejected() {
+    let (mut svm, user) = setup();
+    set_kyc(&mut svm, &user.pubkey(), KycStatus::Revoked, NOW + 3600, REASON_FRAUD);
+
+    assert_zord_error(
+        gated_deposit(&mut svm, &user, kyc_pda(&user.pubkey())),
+        ZordError::KycRevoked,
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy from `anchor build`"]
+fn someone_elses_kyc_account_is_rejected() {
+    let (mut svm, user) = setup();
+    let other = Pubkey::new_unique();
+    set_kyc(&mut svm, &other, KycStatus::Verified, NOW + 3600, REASON_NONE);
+
+    assert_zord_error(
+        gated_deposit(&mut svm, &user, kyc_pda(&other)),
+        ZordError::InvalidKycAccount,
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy from `anchor build`"]
+fn account_not_owned_by_zord_is_rejected() {
+    let (mut svm, user) = setup();
+    set_kyc(&mut svm, &user.pubkey(), KycStatus::Verified, NOW + 3600, REASON_NONE);
+
+    let pda = kyc_pda(&user.pubkey());
+    let mut forged = svm.get_account(&pda).unwrap();
+    forged.owner = system_program::ID;
+    svm.set_account(pda, forged).unwrap();
+
+    assert_zord_error(
+        gated_deposit(&mut svm, &user, pda),
+        ZordError::InvalidKycAccount,
+    );
+}
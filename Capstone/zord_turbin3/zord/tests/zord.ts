@@ -2,8 +2,11 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program, AnchorProvider, web3 } from "@coral-xyz/anchor";
 import { expect } from "chai";
 import os from "os";
+import fs from "fs";
+import path from "path";
+import crypto from "crypto";
 
-const { SystemProgram, PublicKey, Keypair } = web3;
+const { SystemProgram, PublicKey, Keypair, Ed25519Program, SYSVAR_INSTRUCTIONS_PUBKEY } = web3;
 
 // Default environment vars
 if (!process.env.ANCHOR_PROVIDER_URL) {
@@ -13,6 +16,104 @@ if (!process.env.ANCHOR_WALLET) {
   process.env.ANCHOR_WALLET = os.homedir() + "/.config/solana/id.json";
 }
 
+const ZK_DIR = path.join(__dirname, "../../zk/passport_pan_zk");
+const VK_VERSION = 1;
+// public signals of proof.circom: valid, nullifier, user_key_commitment
+const NULLIFIER_INDEX = 1;
+
+// Field element (decimal string) -> 32 byte big-endian
+function fieldToBytes(value: string): Buffer {
+  return Buffer.from(BigInt(value).toString(16).padStart(64, "0"), "hex");
+}
+
+// snarkjs proof.json -> A || B || C in the alt_bn128 layout `verify_zk` expects
+function encodeProof(proof: any): Buffer {
+  return Buffer.concat([
+    fieldToBytes(proof.pi_a[0]),
+    fieldToBytes(proof.pi_a[1]),
+    fieldToBytes(proof.pi_b[0][1]),
+    fieldToBytes(proof.pi_b[0][0]),
+    fieldToBytes(proof.pi_b[1][1]),
+    fieldToBytes(proof.pi_b[1][0]),
+    fieldToBytes(proof.pi_c[0]),
+    fieldToBytes(proof.pi_c[1]),
+  ]);
+}
+
+// Artifacts must be generated with USER_PUBKEY set to the provider wallet
+function loadProof(): { proofBytes: Buffer; publicInputs: number[][] } {
+  const proof = JSON.parse(fs.readFileSync(path.join(ZK_DIR, "proof.json"), "utf8"));
+  const publicSignals: string[] = JSON.parse(
+    fs.readFileSync(path.join(ZK_DIR, "public.json"), "utf8")
+  );
+  return {
+    proofBytes: encodeProof(proof),
+    publicInputs: publicSignals.map((s) => Array.from(fieldToBytes(s))),
+  };
+}
+
+// Deterministic so repeated runs reuse the same allow-listed attester
+const attester = Keypair.fromSeed(
+  crypto.createHash("sha256").update("zord-test-attester").digest()
+);
+
+const BPF_LOADER_UPGRADEABLE = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+const DOC_PASSPORT = 1;
+const DOC_PAN = 2;
+
+function sha256(data: Buffer | string): Buffer {
+  return crypto.createHash("sha256").update(data).digest();
+}
+
+function makeAttestation(subject: web3.PublicKey, overrides: any = {}) {
+  const now = Math.floor(Date.now() / 1000);
+  return {
+    version: 2,
+    subject,
+    issuer: attester.publicKey,
+    issuedAt: new anchor.BN(now - 60),
+    expiresAt: new anchor.BN(now + 365 * 24 * 60 * 60),
+    documentFlags: DOC_PASSPORT | DOC_PAN,
+    claimsCommitment: Array.from(crypto.randomBytes(32)),
+    documentCommitment: Array.from(crypto.randomBytes(32)),
+    ...overrides,
+  };
+}
+
+// sha256 of the Borsh encoding of `Attestation`, as hashed on-chain
+function attestationHash(a: any): Buffer {
+  const i64 = (n: anchor.BN) => {
+    const b = Buffer.alloc(8);
+    b.writeBigInt64LE(BigInt(n.toString()));
+    return b;
+  };
+  return sha256(
+    Buffer.concat([
+      Buffer.from([a.version]),
+      a.subject.toBuffer(),
+      a.issuer.toBuffer(),
+      i64(a.issuedAt),
+      i64(a.expiresAt),
+      Buffer.from([a.documentFlags]),
+      Buffer.from(a.claimsCommitment),
+      Buffer.from(a.documentCommitment),
+    ])
+  );
+}
+
+// Ed25519 precompile instruction over `user || attestation_hash`
+function signAttestation(
+  owner: web3.PublicKey,
+  hash: Buffer,
+  signer: web3.Keypair = attester
+): web3.TransactionInstruction {
+  return Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: signer.secretKey,
+    message: Buffer.concat([owner.toBuffer(), hash]),
+  });
+}
+
 describe("Zord KYC Program Tests", () => {
   const provider = AnchorProvider.env();
   anchor.setProvider(provider);
@@ -22,6 +123,17 @@ describe("Zord KYC Program Tests", () => {
 
   let kycPda: web3.PublicKey;
   let vkPda: web3.PublicKey;
+  let attesterPda: web3.PublicKey;
+  // The configs are initialized by the upgrade authority, `anchor test` deploys with `user`
+  let programDataPda: web3.PublicKey;
+
+  // [b"nullifier", public_inputs[NULLIFIER_INDEX]], empty seed when the signal is missing
+  function nullifierPda(publicInputs: number[][]): web3.PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("nullifier"), Buffer.from(publicInputs[NULLIFIER_INDEX] ?? [])],
+      program.programId
+    )[0];
+  }
 
   before(async () => {
     // Derive PDAs
@@ -30,11 +142,43 @@ describe("Zord KYC Program Tests", () => {
       program.programId
     );
 
+    [programDataPda] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE
+    );
+
     [vkPda] = await PublicKey.findProgramAddress(
       [Buffer.from("vk")],
       program.programId
     );
 
+    [attesterPda] = await PublicKey.findProgramAddress(
+      [Buffer.from("attesters")],
+      program.programId
+    );
+
+    // Allow-list the test attester (config and entry may already exist)
+    try {
+      await (program.rpc as any).initializeAttesters({
+        accounts: {
+          admin: user,
+          attesterConfig: attesterPda,
+          program: program.programId,
+          programData: programDataPda,
+          systemProgram: SystemProgram.programId,
+        },
+      });
+    } catch (e) {
+      // Account may already exist
+    }
+    try {
+      await (program.rpc as any).addAttester(attester.publicKey, {
+        accounts: { admin: user, attesterConfig: attesterPda },
+      });
+    } catch (e) {
+      // Attester may already be allowed
+    }
+
     console.log("Program ID:", program.programId.toString());
     console.log("User:", user.toString());
     console.log("KYC PDA:", kycPda.toString());
@@ -55,7 +199,7 @@ describe("Zord KYC Program Tests", () => {
         const kycAccount = await (program.account as any).kycAccount.fetch(kycPda);
         
         expect(kycAccount.user.toString()).to.equal(user.toString());
-        expect(kycAccount.isVerified).to.equal(false);
+        expect(kycAccount.status).to.deep.equal({ pending: {} });
         expect(kycAccount.timestamp.toNumber()).to.equal(0);
         
         console.log("✓ KYC account initialized successfully");
@@ -87,81 +231,49 @@ describe("Zord KYC Program Tests", () => {
   });
 
   describe("Submit Attestation", () => {
+    const accounts = () => ({
+      user,
+      kycAccount: kycPda,
+      attesterConfig: attesterPda,
+      instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+    });
+
     it("should submit a valid attestation", async () => {
-      const attestation = JSON.stringify({
-        is_valid: true,
-        passport_hash: "abc123def456",
-        pan_hash: "789xyz012",
-        proof_generated_at: new Date().toISOString(),
-      });
+      const attestation = makeAttestation(user);
 
       await (program.rpc as any).submitAttestation(attestation, {
-        accounts: {
-          user,
-          kycAccount: kycPda,
-        },
+        accounts: accounts(),
+        instructions: [signAttestation(user, attestationHash(attestation))],
       });
 
       const kycAccount = await (program.account as any).kycAccount.fetch(kycPda);
       
-      expect(kycAccount.isVerified).to.equal(true);
+      expect(kycAccount.status).to.deep.equal({ verified: {} });
       expect(kycAccount.timestamp.toNumber()).to.be.greaterThan(0);
-      expect(Array.from(kycAccount.attestationHash)).to.not.deep.equal(Array(32).fill(0));
+      expect(Buffer.from(kycAccount.attestationHash)).to.deep.equal(attestationHash(attestation));
+      expect(kycAccount.expiresAt.toString()).to.equal(attestation.expiresAt.toString());
+      expect(kycAccount.attester.toString()).to.equal(attester.publicKey.toString());
       
-      console.log("✓ Attestation submitted with is_valid: true");
+      console.log("✓ Attestation submitted");
       console.log("  Timestamp:", kycAccount.timestamp.toNumber());
     });
 
-    it("should submit attestation with is_valid: false", async () => {
-      const attestation = JSON.stringify({
-        is_valid: false,
-        passport_hash: "invalid_hash",
-        pan_hash: "invalid_pan",
-        proof_generated_at: new Date().toISOString(),
-      });
-
-      await (program.rpc as any).submitAttestation(attestation, {
-        accounts: {
-          user,
-          kycAccount: kycPda,
-        },
-      });
-
-      const kycAccount = await (program.account as any).kycAccount.fetch(kycPda);
-      
-      expect(kycAccount.isVerified).to.equal(false);
-      console.log("✓ Attestation submitted with is_valid: false");
-    });
-
     it("should update attestation hash on new submission", async () => {
-      const attestation1 = JSON.stringify({
-        is_valid: true,
-        data: "first_submission",
-      });
+      const attestation1 = makeAttestation(user);
 
       await (program.rpc as any).submitAttestation(attestation1, {
-        accounts: {
-          user,
-          kycAccount: kycPda,
-        },
+        accounts: accounts(),
+        instructions: [signAttestation(user, attestationHash(attestation1))],
       });
 
       const account1 = await (program.account as any).kycAccount.fetch(kycPda);
       const hash1 = Array.from(account1.attestationHash);
 
-      // Wait a bit to ensure different timestamp
-      await new Promise(resolve => setTimeout(resolve, 1000));
-
-      const attestation2 = JSON.stringify({
-        is_valid: true,
-        data: "second_submission",
-      });
+      const attestation2 = makeAttestation(user);
 
       await (program.rpc as any).submitAttestation(attestation2, {
-        accounts: {
-          user,
-          kycAccount: kycPda,
-        },
+        accounts: accounts(),
+        instructions: [signAttestation(user, attestationHash(attestation2))],
       });
 
       const account2 = await (program.account as any).kycAccount.fetch(kycPda);
@@ -171,6 +283,82 @@ describe("Zord KYC Program Tests", () => {
       console.log("✓ Attestation hash updated correctly");
     });
 
+    it("should fail without an attester signature", async () => {
+      const attestation = makeAttestation(user);
+
+      try {
+        await (program.rpc as any).submitAttestation(attestation, {
+          accounts: accounts(),
+        });
+
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MissingAttesterSignature");
+        console.log("✓ Correctly rejects unsigned attestation");
+      }
+    });
+
+    it("should fail when signed by an unknown attester", async () => {
+      const rogue = Keypair.generate();
+      const attestation = makeAttestation(user, { issuer: rogue.publicKey });
+
+      try {
+        await (program.rpc as any).submitAttestation(attestation, {
+          accounts: accounts(),
+          instructions: [signAttestation(user, attestationHash(attestation), rogue)],
+        });
+
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("UnknownAttester");
+        console.log("✓ Correctly rejects unknown attester");
+      }
+    });
+
+    it("should fail when the attestation is for another subject", async () => {
+      const attestation = makeAttestation(Keypair.generate().publicKey);
+
+      try {
+        await (program.rpc as any).submitAttestation(attestation, {
+          accounts: accounts(),
+          instructions: [signAttestation(user, attestationHash(attestation))],
+        });
+
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AttestationSubjectMismatch");
+        console.log("✓ Correctly rejects attestation for another subject");
+      }
+    });
+
+    it("should fail with an expired attestation", async () => {
+      const now = Math.floor(Date.now() / 1000);
+      const attestation = makeAttestation(user, {
+        issuedAt: new anchor.BN(now - 7200),
+        expiresAt: new anchor.BN(now - 3600),
+      });
+
+      try {
+        await (program.rpc as any).submitAttestation(attestation, {
+          accounts: accounts(),
+          instructions: [signAttestation(user, attestationHash(attestation))],
+        });
+
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AttestationExpired");
+        console.log("✓ Correctly rejects expired attestation");
+      }
+    });
+  });
+
+  // Only available when the program is built with `--features json-attestation`,
+  // which swaps the typed submit_attestation for the legacy JSON one
+  describe("Submit Attestation (legacy JSON)", () => {
+    before(function () {
+      if (!process.env.ZORD_JSON_ATTESTATION) this.skip();
+    });
+
     it("should fail with invalid JSON", async () => {
       const invalidJson = "{ this is not valid json }";
 
@@ -179,7 +367,10 @@ describe("Zord KYC Program Tests", () => {
           accounts: {
             user,
             kycAccount: kycPda,
+            attesterConfig: attesterPda,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
           },
+          instructions: [signAttestation(user, sha256(invalidJson))],
         });
         
         throw new Error("Should have failed");
@@ -200,7 +391,10 @@ describe("Zord KYC Program Tests", () => {
           accounts: {
             user,
             kycAccount: kycPda,
+            attesterConfig: attesterPda,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
           },
+          instructions: [signAttestation(user, sha256(missingField))],
         });
         
         throw new Error("Should have failed");
@@ -209,43 +403,64 @@ describe("Zord KYC Program Tests", () => {
         console.log("✓ Correctly rejects JSON missing is_valid");
       }
     });
+
+    it("should fail when is_valid is false", async () => {
+      const rejected = JSON.stringify({ is_valid: false });
+
+      try {
+        await (program.rpc as any).submitAttestation(rejected, {
+          accounts: {
+            user,
+            kycAccount: kycPda,
+            attesterConfig: attesterPda,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          },
+          instructions: [signAttestation(user, sha256(rejected))],
+        });
+
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AttestationNotValid");
+        console.log("✓ Correctly rejects an attestation with is_valid false");
+      }
+    });
   });
 
   describe("Verify ZK Proof", () => {
     before(async () => {
-      // Ensure vk account exists for tests
+      // Ensure the verifier config (with the genesis key) exists for tests
       try {
-        const lamports = await provider.connection.getMinimumBalanceForRentExemption(8);
-        const createIx = SystemProgram.createAccount({
-          fromPubkey: user,
-          newAccountPubkey: vkPda,
-          space: 8,
-          lamports,
-          programId: program.programId,
+        await (program.rpc as any).initializeVerifier({
+          accounts: {
+            admin: user,
+            verifierConfig: vkPda,
+            program: program.programId,
+            programData: programDataPda,
+            systemProgram: SystemProgram.programId,
+          },
         });
-
-        const tx = new web3.Transaction().add(createIx);
-        await provider.sendAndConfirm(tx, []);
       } catch (e) {
         // Account may already exist
       }
     });
 
     it("should verify a valid ZK proof", async () => {
-      const proofBytes = Buffer.from("valid_proof_data_123");
-      const publicInputs = [
-        Array.from(Buffer.alloc(32, 1)),
-        Array.from(Buffer.alloc(32, 2)),
-      ];
+      const { proofBytes, publicInputs } = loadProof();
 
-      await (program.rpc as any).verifyZk(proofBytes, publicInputs, {
+      await (program.rpc as any).verifyZk(VK_VERSION, proofBytes, publicInputs, {
         accounts: {
-          vkAccount: vkPda,
-          signer: user,
+          verifierConfig: vkPda,
+          kycAccount: kycPda,
+          nullifierRecord: nullifierPda(publicInputs),
+          user,
           systemProgram: SystemProgram.programId,
         },
       });
 
+      const kycAccount = await (program.account as any).kycAccount.fetch(kycPda);
+      expect(kycAccount.status).to.deep.equal({ verified: {} });
+      expect(kycAccount.verifiedVkVersion).to.equal(VK_VERSION);
+
       console.log("✓ ZK proof verified successfully");
     });
 
@@ -254,10 +469,12 @@ describe("Zord KYC Program Tests", () => {
       const publicInputs: number[][] = [];
 
       try {
-        await (program.rpc as any).verifyZk(emptyProof, publicInputs, {
+        await (program.rpc as any).verifyZk(VK_VERSION, emptyProof, publicInputs, {
           accounts: {
-            vkAccount: vkPda,
-            signer: user,
+            verifierConfig: vkPda,
+            kycAccount: kycPda,
+            nullifierRecord: nullifierPda(publicInputs),
+            user,
             systemProgram: SystemProgram.programId,
           },
         });
@@ -269,21 +486,71 @@ describe("Zord KYC Program Tests", () => {
       }
     });
 
-    it("should handle large proof data", async () => {
+    it("should reject garbage proof data", async () => {
       const largeProof = Buffer.alloc(256, 0xAB); // Reduced from 1024 to fit in transaction
       const publicInputs = [
         Array.from(Buffer.alloc(32, 0xFF)),
       ];
 
-      await (program.rpc as any).verifyZk(largeProof, publicInputs, {
-        accounts: {
-          vkAccount: vkPda,
-          signer: user,
-          systemProgram: SystemProgram.programId,
-        },
-      });
+      try {
+        await (program.rpc as any).verifyZk(VK_VERSION, largeProof, publicInputs, {
+          accounts: {
+            verifierConfig: vkPda,
+            kycAccount: kycPda,
+            nullifierRecord: nullifierPda(publicInputs),
+            user,
+            systemProgram: SystemProgram.programId,
+          },
+        });
 
-      console.log("✓ Large proof data handled correctly");
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ProofDeserialize");
+        console.log("✓ Correctly rejects garbage proof data");
+      }
+    });
+
+    it("should fail with an unregistered vk version", async () => {
+      const { proofBytes, publicInputs } = loadProof();
+
+      try {
+        await (program.rpc as any).verifyZk(999, proofBytes, publicInputs, {
+          accounts: {
+            verifierConfig: vkPda,
+            kycAccount: kycPda,
+            nullifierRecord: nullifierPda(publicInputs),
+            user,
+            systemProgram: SystemProgram.programId,
+          },
+        });
+
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("VerificationKeyMissing");
+        console.log("✓ Correctly rejects unknown vk version");
+      }
+    });
+
+    it("should fail when public inputs do not match the proof", async () => {
+      const { proofBytes, publicInputs } = loadProof();
+      publicInputs[0] = Array.from(fieldToBytes("2"));
+
+      try {
+        await (program.rpc as any).verifyZk(VK_VERSION, proofBytes, publicInputs, {
+          accounts: {
+            verifierConfig: vkPda,
+            kycAccount: kycPda,
+            nullifierRecord: nullifierPda(publicInputs),
+            user,
+            systemProgram: SystemProgram.programId,
+          },
+        });
+
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("VerificationFailed");
+        console.log("✓ Correctly rejects mismatched public inputs");
+      }
     });
   });
 
@@ -315,43 +582,182 @@ describe("Zord KYC Program Tests", () => {
       });
 
       let account = await (program.account as any).kycAccount.fetch(newKycPda);
-      expect(account.isVerified).to.equal(false);
+      expect(account.status).to.deep.equal({ pending: {} });
       console.log("  ✓ Step 1: KYC initialized");
 
       // Step 2: Submit attestation
-      const attestation = JSON.stringify({
-        is_valid: true,
-        passport_hash: "new_user_passport",
-        pan_hash: "new_user_pan",
-      });
+      const attestation = makeAttestation(newUser.publicKey);
 
       await (program.rpc as any).submitAttestation(attestation, {
         accounts: {
           user: newUser.publicKey,
           kycAccount: newKycPda,
+          attesterConfig: attesterPda,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
         },
+        instructions: [signAttestation(newUser.publicKey, attestationHash(attestation))],
         signers: [newUser],
       });
 
       account = await (program.account as any).kycAccount.fetch(newKycPda);
-      expect(account.isVerified).to.equal(true);
+      expect(account.status).to.deep.equal({ verified: {} });
       console.log("  ✓ Step 2: Attestation submitted");
 
-      // Step 3: Verify ZK proof
-      const proofBytes = Buffer.from("new_user_proof");
-      const publicInputs: number[][] = [];
+      // Step 3: The reference proof is bound to the provider wallet, not this user
+      const { proofBytes, publicInputs } = loadProof();
+
+      try {
+        await (program.rpc as any).verifyZk(VK_VERSION, proofBytes, publicInputs, {
+          accounts: {
+            verifierConfig: vkPda,
+            kycAccount: newKycPda,
+            nullifierRecord: nullifierPda(publicInputs),
+            user: newUser.publicKey,
+            systemProgram: SystemProgram.programId,
+          },
+          signers: [newUser],
+        });
+
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("UserKeyMismatch");
+      }
+
+      console.log("  ✓ Step 3: Proof for another wallet rejected");
+      console.log("✓ Complete KYC flow successful for new user");
+    });
+  });
+
+  describe("KYC Lifecycle", () => {
+    const REASON_FRAUD = 1;
+    const subject = Keypair.generate();
+    let subjectKyc: web3.PublicKey;
 
-      await (program.rpc as any).verifyZk(proofBytes, publicInputs, {
+    let original: any;
+
+    const attest = async (attestation: any = makeAttestation(subject.publicKey)) => {
+      await (program.rpc as any).submitAttestation(attestation, {
         accounts: {
-          vkAccount: vkPda,
-          signer: newUser.publicKey,
+          user: subject.publicKey,
+          kycAccount: subjectKyc,
+          attesterConfig: attesterPda,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        },
+        instructions: [signAttestation(subject.publicKey, attestationHash(attestation))],
+        signers: [subject],
+      });
+      return attestation;
+    };
+
+    before(async () => {
+      const signature = await provider.connection.requestAirdrop(
+        subject.publicKey,
+        web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(signature);
+
+      [subjectKyc] = await PublicKey.findProgramAddress(
+        [Buffer.from("kyc"), subject.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await (program.rpc as any).initializeKyc({
+        accounts: {
+          user: subject.publicKey,
+          kycAccount: subjectKyc,
           systemProgram: SystemProgram.programId,
         },
-        signers: [newUser],
+        signers: [subject],
       });
+      original = await attest();
+    });
 
-      console.log("  ✓ Step 3: ZK proof verified");
-      console.log("✓ Complete KYC flow successful for new user");
+    it("should not expire a verification before its expiry", async () => {
+      try {
+        await (program.rpc as any).expireKyc({
+          accounts: { kycAccount: subjectKyc },
+        });
+
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("KycNotExpired");
+      }
+    });
+
+    it("should let the admin suspend and an attester re-verify", async () => {
+      await (program.rpc as any).suspendKyc(REASON_FRAUD, {
+        accounts: { authority: user, attesterConfig: attesterPda, kycAccount: subjectKyc },
+      });
+
+      let account = await (program.account as any).kycAccount.fetch(subjectKyc);
+      expect(account.status).to.deep.equal({ suspended: {} });
+      expect(account.revocationReason).to.equal(REASON_FRAUD);
+
+      // replaying the attestation the record was verified with does not lift it
+      try {
+        await attest(original);
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("StaleAttestation");
+      }
+
+      // `timestamp` holds the suspension time; wait for the clock to pass it
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      await attest(
+        makeAttestation(subject.publicKey, { issuedAt: account.timestamp.addn(1) })
+      );
+
+      account = await (program.account as any).kycAccount.fetch(subjectKyc);
+      expect(account.status).to.deep.equal({ verified: {} });
+      expect(account.revocationReason).to.equal(0);
+      console.log("✓ Suspension lifted by a fresh attestation");
+    });
+
+    it("should reject suspension by a non-authority", async () => {
+      try {
+        await (program.rpc as any).suspendKyc(REASON_FRAUD, {
+          accounts: {
+            authority: subject.publicKey,
+            attesterConfig: attesterPda,
+            kycAccount: subjectKyc,
+          },
+          signers: [subject],
+        });
+
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+      }
+    });
+
+    it("should make revocation terminal", async () => {
+      const signature = await (program.rpc as any).revokeKyc(REASON_FRAUD, {
+        accounts: { authority: user, attesterConfig: attesterPda, kycAccount: subjectKyc },
+      });
+
+      // indexers rebuild history from these events, not from account snapshots
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      const events = [...parser.parseLogs(tx!.meta!.logMessages!)];
+      const revoked = events.find((e) => e.name.toLowerCase() === "kycrevoked");
+      expect(revoked, "KycRevoked event").to.not.be.undefined;
+      expect(revoked!.data.user.toString()).to.equal(subject.publicKey.toString());
+      expect(revoked!.data.reason).to.equal(REASON_FRAUD);
+
+      const account = await (program.account as any).kycAccount.fetch(subjectKyc);
+      expect(account.status).to.deep.equal({ revoked: {} });
+      expect(account.revocationReason).to.equal(REASON_FRAUD);
+
+      try {
+        await attest();
+        throw new Error("Should have failed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("KycRevoked");
+        console.log("✓ Revoked record cannot be re-verified");
+      }
     });
   });
 });
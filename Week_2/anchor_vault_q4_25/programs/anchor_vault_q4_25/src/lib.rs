@@ -1,12 +1,20 @@
 use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token, token_2022,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
 declare_id!("FQZTszJjBr5jWzb3bQpAvspjF9YH6fPBqGFS2kYQ69HF");
 
 #[program]
 pub mod anchor_vault_q4_25 {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        ctx.accounts.initialize(&ctx.bumps)
+    pub fn initialize(ctx: Context<Initialize>, lock: Lock) -> Result<()> {
+        ctx.accounts.initialize(lock, &ctx.bumps)
     }
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         ctx.accounts.deposit(amount)
@@ -14,8 +22,34 @@ pub mod anchor_vault_q4_25 {
     pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
         ctx.accounts.withdraw(amount)
     }
-    pub fn close(ctx: Context<Close>) -> Result<()> {
-        ctx.accounts.close()
+    // Remaining accounts: (mint, vault token account, user token account, token program)
+    // for every mint still in `vault_state.mints`, each drained and closed before the vault.
+    pub fn close<'info>(ctx: Context<'_, '_, 'info, 'info, Close<'info>>) -> Result<()> {
+        ctx.accounts.close(ctx.remaining_accounts)
+    }
+    pub fn tighten_lock(ctx: Context<TightenLock>, lock: Lock) -> Result<()> {
+        ctx.accounts.tighten_lock(lock)
+    }
+    pub fn deposit_spl(ctx: Context<DepositSpl>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit_spl(amount)
+    }
+    pub fn withdraw_spl(ctx: Context<WithdrawSpl>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_spl(amount)
+    }
+    pub fn close_spl(ctx: Context<CloseSpl>) -> Result<()> {
+        ctx.accounts.close_spl()
+    }
+    pub fn approve(ctx: Context<Approve>, cap: u64, period: i64) -> Result<()> {
+        ctx.accounts.approve(cap, period, &ctx.bumps)
+    }
+    pub fn revoke(ctx: Context<Revoke>) -> Result<()> {
+        ctx.accounts.revoke()
+    }
+    pub fn delegate_withdraw(ctx: Context<DelegateWithdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.delegate_withdraw(amount)
+    }
+    pub fn migrate(ctx: Context<Migrate>) -> Result<()> {
+        ctx.accounts.migrate()
     }
 
 }
@@ -41,7 +75,7 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 impl<'info> Initialize<'info> {
-    pub fn initialize(&mut self, bump: &InitializeBumps) -> Result<()> {
+    pub fn initialize(&mut self, lock: Lock, bump: &InitializeBumps) -> Result<()> {
 
         let rent_exempt = Rent::get()?.minimum_balance(self.vault.to_account_info().data_len());
 
@@ -58,6 +92,13 @@ impl<'info> Initialize<'info> {
 
         self.vault_state.state_bump = bump.vault_state;
         self.vault_state.vault_bump = bump.vault;
+        self.vault_state.version = VAULT_STATE_VERSION;
+        self.vault_state.lock = lock;
+        self.vault_state.total_deposited = 0;
+        self.vault_state.total_withdrawn = 0;
+        self.vault_state.last_activity_slot = Clock::get()?.slot;
+        self.vault_state.mints = Vec::new();
+        self.vault_state.allowances = 0;
         Ok(())
     }
 }
@@ -67,6 +108,7 @@ pub struct Deposit<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(
+        mut,
         seeds = [b"state", user.key().as_ref()],
         bump = vault_state.state_bump
     )]
@@ -92,6 +134,13 @@ impl<'info> Deposit<'info> {
         let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
 
         transfer(cpi_context, amount)?;
+
+        self.vault_state.total_deposited = self
+            .vault_state
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        self.vault_state.last_activity_slot = Clock::get()?.slot;
         Ok(())
     }
 }
@@ -101,6 +150,7 @@ pub struct Withdraw<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(
+        mut,
         seeds = [b"state", user.key().as_ref()],
         bump = vault_state.state_bump
     )]
@@ -116,6 +166,15 @@ pub struct Withdraw<'info> {
 impl<'info> Withdraw<'info> {
     pub fn withdraw(&mut self, amount: u64) -> Result<()> {
 
+        let balance = self.vault.lamports();
+        self.vault_state.lock.check(balance, Clock::get()?.unix_timestamp)?;
+
+        check_withdrawal(
+            balance,
+            amount,
+            Rent::get()?.minimum_balance(self.vault.to_account_info().data_len()),
+        )?;
+
         let cpi_program = self.system_program.to_account_info();
 
         let cpi_accounts = Transfer {
@@ -132,6 +191,13 @@ impl<'info> Withdraw<'info> {
         let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
 
         transfer(cpi_context, amount)?;
+
+        self.vault_state.total_withdrawn = self
+            .vault_state
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        self.vault_state.last_activity_slot = Clock::get()?.slot;
         Ok(())
     }
 }
@@ -156,9 +222,72 @@ pub struct Close<'info> {
     pub system_program: Program<'info, System>,
 }
 impl<'info> Close<'info> {
-    pub fn close(&mut self) -> Result<()> {
+    pub fn close(&mut self, token_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
 
         let amount = self.vault.lamports();
+        self.vault_state.lock.check(amount, Clock::get()?.unix_timestamp)?;
+
+        // the vault address comes back on the next `initialize`, and so would
+        // any allowance left open against it
+        require!(
+            self.vault_state.allowances == 0,
+            VaultError::AllowancesOpen
+        );
+
+        let bump_seed = self.vault_state.vault_bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            self.vault_state.to_account_info().key.as_ref(),
+            &[bump_seed],
+        ]];
+
+        require!(
+            token_accounts.len() % TOKEN_ACCOUNTS_PER_MINT == 0,
+            VaultError::InvalidTokenAccounts
+        );
+        for accounts in token_accounts.chunks_exact(TOKEN_ACCOUNTS_PER_MINT) {
+            let [mint, vault_token_account, user_token_account, token_program] = accounts else {
+                unreachable!()
+            };
+            require!(
+                token_program.key() == token::ID || token_program.key() == token_2022::ID,
+                VaultError::InvalidTokenAccounts
+            );
+            require_keys_eq!(
+                vault_token_account.key(),
+                get_associated_token_address_with_program_id(
+                    &self.vault.key(),
+                    &mint.key(),
+                    &token_program.key()
+                ),
+                VaultError::InvalidTokenAccounts
+            );
+            require_keys_eq!(
+                user_token_account.key(),
+                get_associated_token_address_with_program_id(
+                    &self.user.key(),
+                    &mint.key(),
+                    &token_program.key()
+                ),
+                VaultError::InvalidTokenAccounts
+            );
+
+            drain_token_account(
+                token_program.clone(),
+                &InterfaceAccount::try_from(mint)?,
+                &InterfaceAccount::try_from(vault_token_account)?,
+                user_token_account.clone(),
+                self.vault.to_account_info(),
+                self.user.to_account_info(),
+                signer_seeds,
+            )?;
+            self.vault_state.mints.retain(|open| *open != mint.key());
+        }
+        // a vault token account left behind would lose its authority with the vault
+        require!(
+            self.vault_state.mints.is_empty(),
+            VaultError::TokenAccountsOpen
+        );
 
         let cpi_program = self.system_program.to_account_info();
 
@@ -166,6 +295,366 @@ impl<'info> Close<'info> {
             from: self.vault.to_account_info(),
             to: self.user.to_account_info()
         };
+
+        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer(cpi_context, amount)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct TightenLock<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+impl<'info> TightenLock<'info> {
+    pub fn tighten_lock(&mut self, lock: Lock) -> Result<()> {
+
+        self.vault_state.lock = self.vault_state.lock.tighten(lock)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct DepositSpl<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+impl<'info> DepositSpl<'info> {
+    pub fn deposit_spl(&mut self, amount: u64) -> Result<()> {
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.user_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.vault_token_account.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_context, amount, self.mint.decimals)?;
+
+        let mint = self.mint.key();
+        if !self.vault_state.mints.contains(&mint) {
+            require!(
+                self.vault_state.mints.len() < MAX_MINTS,
+                VaultError::TooManyMints
+            );
+            self.vault_state.mints.push(mint);
+        }
+        self.vault_state.last_activity_slot = Clock::get()?.slot;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSpl<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+impl<'info> WithdrawSpl<'info> {
+    pub fn withdraw_spl(&mut self, amount: u64) -> Result<()> {
+
+        let balance = self.vault.lamports();
+        self.vault_state.lock.check(balance, Clock::get()?.unix_timestamp)?;
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.vault_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.user_token_account.to_account_info(),
+            authority: self.vault.to_account_info(),
+        };
+        let bump_seed = self.vault_state.vault_bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            self.vault_state.to_account_info().key.as_ref(),
+            &[bump_seed],
+        ]];
+
+        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(cpi_context, amount, self.mint.decimals)?;
+
+        self.vault_state.last_activity_slot = Clock::get()?.slot;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseSpl<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+impl<'info> CloseSpl<'info> {
+    pub fn close_spl(&mut self) -> Result<()> {
+
+        let balance = self.vault.lamports();
+        self.vault_state.lock.check(balance, Clock::get()?.unix_timestamp)?;
+
+        let bump_seed = self.vault_state.vault_bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            self.vault_state.to_account_info().key.as_ref(),
+            &[bump_seed],
+        ]];
+
+        drain_token_account(
+            self.token_program.to_account_info(),
+            &self.mint,
+            &self.vault_token_account,
+            self.user_token_account.to_account_info(),
+            self.vault.to_account_info(),
+            self.user.to_account_info(),
+            signer_seeds,
+        )?;
+
+        let mint = self.mint.key();
+        self.vault_state.mints.retain(|open| *open != mint);
+        self.vault_state.last_activity_slot = Clock::get()?.slot;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    /// CHECK: only its key is recorded, it signs `delegate_withdraw`
+    pub delegate: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Allowance::DISCRIMINATOR.len() + Allowance::INIT_SPACE,
+        seeds = [b"allowance", vault.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+    pub system_program: Program<'info, System>,
+}
+impl<'info> Approve<'info> {
+    pub fn approve(&mut self, cap: u64, period: i64, bump: &ApproveBumps) -> Result<()> {
+
+        let opened = self.allowance.set(
+            cap,
+            period,
+            bump.allowance,
+            Clock::get()?.unix_timestamp,
+        )?;
+        if opened {
+            self.vault_state.allowances = self
+                .vault_state
+                .allowances
+                .checked_add(1)
+                .ok_or(VaultError::Overflow)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Revoke<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    /// CHECK: only used to derive the allowance
+    pub delegate: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"allowance", vault.key().as_ref(), delegate.key().as_ref()],
+        bump = allowance.bump,
+        close = user
+    )]
+    pub allowance: Account<'info, Allowance>,
+}
+impl<'info> Revoke<'info> {
+    pub fn revoke(&mut self) -> Result<()> {
+
+        self.vault_state.allowances = self
+            .vault_state
+            .allowances
+            .checked_sub(1)
+            .ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct DelegateWithdraw<'info> {
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+    /// CHECK: the vault owner, only used to derive the vault state
+    pub user: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"allowance", vault.key().as_ref(), delegate.key().as_ref()],
+        bump = allowance.bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+    pub system_program: Program<'info, System>,
+}
+impl<'info> DelegateWithdraw<'info> {
+    pub fn delegate_withdraw(&mut self, amount: u64) -> Result<()> {
+
+        // the owner's locks bind the delegate too
+        let balance = self.vault.lamports();
+        self.vault_state.lock.check(balance, Clock::get()?.unix_timestamp)?;
+
+        self.allowance.spend(amount, Clock::get()?.unix_timestamp)?;
+
+        check_withdrawal(
+            balance,
+            amount,
+            Rent::get()?.minimum_balance(self.vault.to_account_info().data_len()),
+        )?;
+
+        let cpi_program = self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.delegate.to_account_info()
+        };
         let bump_seed = self.vault_state.vault_bump;
         let signer_seeds: &[&[&[u8]]] = &[&[
             b"vault",
@@ -176,13 +665,509 @@ impl<'info> Close<'info> {
         let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
 
         transfer(cpi_context, amount)?;
+
+        self.vault_state.total_withdrawn = self
+            .vault_state
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        self.vault_state.last_activity_slot = Clock::get()?.slot;
+        Ok(())
+    }
+}
+
+// Grows a vault state from before the lock to the current layout, the user pays
+// the extra rent. The vault opens unlocked and its totals count from here.
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: may still hold the original layout, discriminator and length are checked in `migrate`
+    #[account(
+        mut,
+        seeds = [b"state", user.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub vault_state: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+impl<'info> Migrate<'info> {
+    pub fn migrate(&mut self) -> Result<()> {
+
+        let state_info = self.vault_state.to_account_info();
+        let new_len = VaultState::DISCRIMINATOR.len() + VaultState::INIT_SPACE;
+
+        let state = VaultState::from_v0(&state_info.try_borrow_data()?, Clock::get()?.slot)?;
+
+        let shortfall = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(state_info.lamports());
+        if shortfall > 0 {
+            let cpi_program = self.system_program.to_account_info();
+
+            let cpi_accounts = Transfer {
+                from: self.user.to_account_info(),
+                to: state_info.clone()
+            };
+
+            let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
+
+            transfer(cpi_context, shortfall)?;
+        }
+
+        state_info.resize(new_len)?;
+        state.try_serialize(&mut &mut state_info.try_borrow_mut_data()?[..])?;
         Ok(())
     }
 }
 
+// Only `close` may take the vault below rent exemption.
+fn check_withdrawal(balance: u64, amount: u64, rent_exempt: u64) -> Result<()> {
+
+    let remaining = balance
+        .checked_sub(amount)
+        .ok_or(VaultError::InsufficientFunds)?;
+    require!(remaining >= rent_exempt, VaultError::BelowRentExemption);
+    Ok(())
+}
+
+// What `close` expects in its remaining accounts for each mint.
+pub const TOKEN_ACCOUNTS_PER_MINT: usize = 4;
+// Mints a vault may hold at once, few enough for `close` to drain them all in one transaction.
+pub const MAX_MINTS: usize = 5;
+
+// Sends the whole balance of a vault token account to `to`, then closes it
+// with the rent going to `user`.
+fn drain_token_account<'info>(
+    token_program: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault_token_account: &InterfaceAccount<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    user: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+
+    let cpi_accounts = TransferChecked {
+        from: vault_token_account.to_account_info(),
+        mint: mint.to_account_info(),
+        to,
+        authority: vault.clone(),
+    };
+
+    let cpi_context =
+        CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
+
+    transfer_checked(cpi_context, vault_token_account.amount, mint.decimals)?;
+
+    let cpi_accounts = CloseAccount {
+        account: vault_token_account.to_account_info(),
+        destination: user,
+        authority: vault,
+    };
+
+    let cpi_context = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+
+    close_account(cpi_context)
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct VaultState{
     pub vault_bump: u8,
     pub state_bump: u8,
-}
\ No newline at end of file
+    // layout version; the original layout, just the two bumps, has none and goes through `migrate`
+    pub version: u8,
+    pub lock: Lock,
+    // lamports moved by `deposit` and `withdraw`; the rent seed from `initialize` is in neither
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub last_activity_slot: u64,
+    // mints whose vault token account `deposit_spl` opened and `close_spl` has not closed
+    #[max_len(MAX_MINTS)]
+    pub mints: Vec<Pubkey>,
+    // allowances `approve` opened and `revoke` has not closed
+    pub allowances: u32,
+}
+
+impl VaultState {
+    // Reads a vault state in the original layout, discriminator and two bumps.
+    pub fn from_v0(data: &[u8], slot: u64) -> Result<Self> {
+
+        require!(
+            data.starts_with(VaultState::DISCRIMINATOR),
+            VaultError::InvalidVaultState
+        );
+        require!(
+            data.len() == VAULT_STATE_V0_LEN,
+            VaultError::AlreadyMigrated
+        );
+        let [vault_bump, state_bump] = data[VaultState::DISCRIMINATOR.len()..] else {
+            unreachable!()
+        };
+        Ok(Self {
+            vault_bump,
+            state_bump,
+            version: VAULT_STATE_VERSION,
+            lock: Lock::default(),
+            total_deposited: 0,
+            total_withdrawn: 0,
+            last_activity_slot: slot,
+            mints: Vec::new(),
+            allowances: 0,
+        })
+    }
+}
+
+// Layout version `initialize` and `migrate` write.
+pub const VAULT_STATE_VERSION: u8 = 1;
+// Length of a vault state in the original layout.
+pub const VAULT_STATE_V0_LEN: usize = 8 + 2;
+
+// Lamports a delegate may take from the vault per `period` seconds.
+#[account]
+#[derive(InitSpace)]
+pub struct Allowance {
+    pub bump: u8,
+    pub cap: u64,
+    pub period: i64,
+    pub period_start: i64,
+    pub spent: u64,
+}
+impl Allowance {
+    // Returns whether this opened the allowance rather than changed an open one.
+    pub fn set(&mut self, cap: u64, period: i64, bump: u8, now: i64) -> Result<bool> {
+
+        require!(period > 0, VaultError::InvalidPeriod);
+
+        // a new allowance opens its first period now; changing an existing one
+        // keeps what was already spent in the current period
+        let opened = self.period == 0;
+        if opened {
+            self.bump = bump;
+            self.period_start = now;
+            self.spent = 0;
+        }
+        self.cap = cap;
+        self.period = period;
+        Ok(opened)
+    }
+
+    pub fn spend(&mut self, amount: u64, now: i64) -> Result<()> {
+
+        // `approve` never stores a zero period, only an account it has not written has one
+        require!(self.period > 0, VaultError::InvalidPeriod);
+
+        // periods follow each other from the first one, idle periods are skipped over
+        let elapsed = now.saturating_sub(self.period_start);
+        if elapsed >= self.period {
+            self.period_start = self
+                .period_start
+                .checked_add(elapsed - elapsed % self.period)
+                .ok_or(VaultError::Overflow)?;
+            self.spent = 0;
+        }
+
+        let spent = self
+            .spent
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        require!(spent <= self.cap, VaultError::AllowanceExceeded);
+        self.spent = spent;
+        Ok(())
+    }
+}
+
+// Conditions that must all hold before anything leaves the vault, tokens included;
+// `None` means no condition.
+// `target_balance` is counted in lamports, so tokens also wait for the lamport target.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct Lock {
+    pub unlock_at: Option<i64>,
+    pub target_balance: Option<u64>,
+}
+impl Lock {
+    pub fn check(&mut self, vault_balance: u64, now: i64) -> Result<()> {
+
+        // once reached, the target stays reached even as the owner withdraws
+        if self.target_balance.is_some_and(|target| vault_balance >= target) {
+            self.target_balance = None;
+        }
+
+        self.check_time(now)?;
+        require!(self.target_balance.is_none(), VaultError::TargetNotReached);
+        Ok(())
+    }
+
+    pub fn check_time(&self, now: i64) -> Result<()> {
+
+        if let Some(unlock_at) = self.unlock_at {
+            require!(now >= unlock_at, VaultError::TimeLocked);
+        }
+        Ok(())
+    }
+
+    // A later unlock time or a higher target only; a condition can be added but never dropped.
+    pub fn tighten(self, new: Lock) -> Result<Lock> {
+
+        require!(
+            tightens(self.unlock_at, new.unlock_at),
+            VaultError::LockLoosened
+        );
+        require!(
+            tightens(self.target_balance, new.target_balance),
+            VaultError::LockLoosened
+        );
+        Ok(new)
+    }
+}
+
+fn tightens<T: PartialOrd>(old: Option<T>, new: Option<T>) -> bool {
+    match (old, new) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(old), Some(new)) => new >= old,
+    }
+}
+
+#[error_code]
+pub enum VaultError {
+    #[msg("The vault is time-locked")]
+    TimeLocked,
+    #[msg("The vault has not reached its target balance")]
+    TargetNotReached,
+    #[msg("A lock can only be tightened")]
+    LockLoosened,
+    #[msg("The vault holds less than the requested amount")]
+    InsufficientFunds,
+    #[msg("Withdrawal would leave the vault below rent exemption, close it instead")]
+    BelowRentExemption,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Token accounts must come as mint, vault ATA, user ATA and token program")]
+    InvalidTokenAccounts,
+    #[msg("An allowance period must be positive")]
+    InvalidPeriod,
+    #[msg("Amount exceeds what is left of the allowance this period")]
+    AllowanceExceeded,
+    #[msg("The vault already holds as many mints as it can")]
+    TooManyMints,
+    #[msg("Every vault token account must be passed to close")]
+    TokenAccountsOpen,
+    #[msg("Every allowance must be revoked before closing the vault")]
+    AllowancesOpen,
+    #[msg("Not a vault state account")]
+    InvalidVaultState,
+    #[msg("The vault state is already in the current layout")]
+    AlreadyMigrated,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn lock(unlock_at: Option<i64>, target_balance: Option<u64>) -> Lock {
+        Lock {
+            unlock_at,
+            target_balance,
+        }
+    }
+
+    #[test]
+    fn time_lock_opens_at_unlock_at() {
+        let mut time_lock = lock(Some(NOW), None);
+        assert_eq!(
+            time_lock.check(0, NOW - 1).unwrap_err(),
+            VaultError::TimeLocked.into()
+        );
+        time_lock.check(0, NOW).unwrap();
+        assert_eq!(time_lock, lock(Some(NOW), None));
+    }
+
+    #[test]
+    fn reached_target_is_cleared_for_good() {
+        let mut goal = lock(None, Some(100));
+        assert_eq!(
+            goal.check(99, NOW).unwrap_err(),
+            VaultError::TargetNotReached.into()
+        );
+        assert_eq!(goal.target_balance, Some(100));
+
+        goal.check(100, NOW).unwrap();
+        assert_eq!(goal.target_balance, None);
+        goal.check(0, NOW).unwrap();
+    }
+
+    #[test]
+    fn combined_lock_reports_the_time_lock_first() {
+        let mut combined = lock(Some(NOW), Some(100));
+        assert_eq!(
+            combined.check(0, NOW - 1).unwrap_err(),
+            VaultError::TimeLocked.into()
+        );
+        // the target is reached while still time-locked, and stays reached
+        assert_eq!(
+            combined.check(100, NOW - 1).unwrap_err(),
+            VaultError::TimeLocked.into()
+        );
+        combined.check(0, NOW).unwrap();
+    }
+
+    #[test]
+    fn tighten_only_moves_one_way() {
+        let current = lock(Some(NOW), Some(100));
+
+        assert_eq!(
+            current.tighten(lock(Some(NOW + 1), Some(100))).unwrap(),
+            lock(Some(NOW + 1), Some(100))
+        );
+        assert_eq!(
+            current.tighten(lock(Some(NOW), Some(101))).unwrap(),
+            lock(Some(NOW), Some(101))
+        );
+        for loosened in [
+            lock(Some(NOW - 1), Some(100)),
+            lock(Some(NOW), Some(99)),
+            lock(None, Some(100)),
+            lock(Some(NOW), None),
+        ] {
+            assert_eq!(
+                current.tighten(loosened).unwrap_err(),
+                VaultError::LockLoosened.into()
+            );
+        }
+
+        // a condition can be added to an open lock
+        assert_eq!(Lock::default().tighten(current).unwrap(), current);
+    }
+
+    fn allowance(cap: u64, period: i64, period_start: i64) -> Allowance {
+        Allowance {
+            bump: 255,
+            cap,
+            period,
+            period_start,
+            spent: 0,
+        }
+    }
+
+    #[test]
+    fn allowance_opens_once_and_keeps_its_period_when_changed() {
+        let mut new = allowance(0, 0, 0);
+        assert!(new.set(100, 86_400, 254, NOW).unwrap());
+        assert_eq!((new.bump, new.period_start), (254, NOW));
+        new.spend(60, NOW + 10).unwrap();
+
+        assert!(!new.set(200, 3_600, 254, NOW + 20).unwrap());
+        assert_eq!(
+            (new.cap, new.period, new.period_start, new.spent),
+            (200, 3_600, NOW, 60)
+        );
+
+        assert_eq!(
+            new.set(100, 0, 254, NOW).unwrap_err(),
+            VaultError::InvalidPeriod.into()
+        );
+        assert_eq!(
+            allowance(0, 0, 0).set(100, -1, 254, NOW).unwrap_err(),
+            VaultError::InvalidPeriod.into()
+        );
+    }
+
+    #[test]
+    fn allowance_spends_up_to_the_cap() {
+        let mut daily = allowance(100, 86_400, NOW);
+        daily.spend(60, NOW).unwrap();
+        daily.spend(40, NOW + 86_399).unwrap();
+        assert_eq!(daily.spent, 100);
+        assert_eq!(
+            daily.spend(1, NOW + 86_399).unwrap_err(),
+            VaultError::AllowanceExceeded.into()
+        );
+        // a refused spend leaves the period as it was
+        assert_eq!((daily.period_start, daily.spent), (NOW, 100));
+        assert_eq!(
+            daily.spend(u64::MAX, NOW).unwrap_err(),
+            VaultError::Overflow.into()
+        );
+    }
+
+    #[test]
+    fn allowance_rolls_over_on_the_period_grid() {
+        let mut daily = allowance(100, 86_400, NOW);
+        daily.spend(100, NOW).unwrap();
+
+        daily.spend(30, NOW + 86_400).unwrap();
+        assert_eq!((daily.period_start, daily.spent), (NOW + 86_400, 30));
+
+        // idle periods are skipped, the new one still starts on the grid
+        daily.spend(100, NOW + 3 * 86_400 + 5).unwrap();
+        assert_eq!((daily.period_start, daily.spent), (NOW + 3 * 86_400, 100));
+
+        // a clock behind the period start stays in the current period
+        assert_eq!(
+            daily.spend(1, NOW).unwrap_err(),
+            VaultError::AllowanceExceeded.into()
+        );
+    }
+
+    #[test]
+    fn allowance_rejects_a_zero_period_and_never_overflows() {
+        assert_eq!(
+            allowance(100, 0, NOW).spend(1, NOW).unwrap_err(),
+            VaultError::InvalidPeriod.into()
+        );
+
+        let mut far = allowance(100, 1, i64::MIN);
+        far.spend(1, i64::MAX).unwrap();
+        assert_eq!(far.period_start, -1);
+
+        allowance(100, i64::MAX, i64::MAX)
+            .spend(1, i64::MIN)
+            .unwrap();
+    }
+
+    #[test]
+    fn original_vault_state_migrates_unlocked() {
+        let mut v0 = VaultState::DISCRIMINATOR.to_vec();
+        v0.extend([254, 253]);
+
+        let state = VaultState::from_v0(&v0, 42).unwrap();
+        assert_eq!(
+            (state.vault_bump, state.state_bump, state.version),
+            (254, 253, VAULT_STATE_VERSION)
+        );
+        assert_eq!(state.lock, Lock::default());
+        assert_eq!(state.last_activity_slot, 42);
+        assert!(state.mints.is_empty());
+
+        let mut current = Vec::new();
+        state.try_serialize(&mut current).unwrap();
+        assert_eq!(
+            VaultState::from_v0(&current, 42).err(),
+            Some(VaultError::AlreadyMigrated.into())
+        );
+        v0[0] ^= 1;
+        assert_eq!(
+            VaultState::from_v0(&v0, 42).err(),
+            Some(VaultError::InvalidVaultState.into())
+        );
+    }
+
+    #[test]
+    fn withdrawals_keep_the_rent_exempt_minimum() {
+        let rent_exempt = 890_880;
+        check_withdrawal(rent_exempt + 10, 10, rent_exempt).unwrap();
+        assert_eq!(
+            check_withdrawal(rent_exempt + 10, 11, rent_exempt).unwrap_err(),
+            VaultError::BelowRentExemption.into()
+        );
+        assert_eq!(
+            check_withdrawal(rent_exempt, rent_exempt + 1, rent_exempt).unwrap_err(),
+            VaultError::InsufficientFunds.into()
+        );
+    }
+}
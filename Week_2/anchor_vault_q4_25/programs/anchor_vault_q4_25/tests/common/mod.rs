@@ -0,0 +1,412 @@
+// Shared LiteSVM fixtures for the tests that run the SBF build of the vault.
+
+#![allow(dead_code)]
+
+use std::path::Path;
+
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::solana_program::instruction::{error::InstructionError, AccountMeta, Instruction};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{
+    system_program, AccountDeserialize, Discriminator, InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token::{
+    self, get_associated_token_address_with_program_id,
+    spl_associated_token_account::instruction::create_associated_token_account,
+};
+use anchor_spl::token_2022::spl_token_2022::{
+    instruction::{initialize_mint2, mint_to},
+    state::Mint,
+};
+use anchor_spl::token_interface::TokenAccount;
+use anchor_vault_q4_25::{Allowance, Lock, VaultError, VaultState};
+use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+use litesvm::LiteSVM;
+use solana_account::Account;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_system_interface::instruction::create_account;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+
+pub const PROGRAM: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../target/deploy/anchor_vault_q4_25.so"
+);
+pub const NOW: i64 = 1_700_000_000;
+pub const SOL: u64 = 1_000_000_000;
+pub const DECIMALS: u8 = 6;
+
+/// A bank with the vault loaded and the clock at `NOW`, plus a funded owner.
+/// Panics when the program has not been built.
+pub fn setup() -> (LiteSVM, Keypair) {
+    assert!(
+        Path::new(PROGRAM).exists(),
+        "{PROGRAM} not found, see the build steps at the top of this test"
+    );
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(anchor_vault_q4_25::ID, PROGRAM)
+        .unwrap();
+    set_time(&mut svm, NOW);
+
+    let owner = Keypair::new();
+    svm.airdrop(&owner.pubkey(), 100 * SOL).unwrap();
+    (svm, owner)
+}
+
+pub fn set_time(svm: &mut LiteSVM, unix_timestamp: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp;
+    svm.set_sysvar(&clock);
+}
+
+pub fn state_pda(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"state", owner.as_ref()], &anchor_vault_q4_25::ID).0
+}
+
+pub fn vault_pda(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"vault", state_pda(owner).as_ref()],
+        &anchor_vault_q4_25::ID,
+    )
+    .0
+}
+
+pub fn allowance_pda(owner: &Pubkey, delegate: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"allowance", vault_pda(owner).as_ref(), delegate.as_ref()],
+        &anchor_vault_q4_25::ID,
+    )
+    .0
+}
+
+pub fn allowance(svm: &LiteSVM, owner: &Pubkey, delegate: &Pubkey) -> Allowance {
+    let account = svm.get_account(&allowance_pda(owner, delegate)).unwrap();
+    Allowance::try_deserialize(&mut &account.data[..]).unwrap()
+}
+
+pub fn vault_state(svm: &LiteSVM, owner: &Pubkey) -> VaultState {
+    let account = svm.get_account(&state_pda(owner)).unwrap();
+    VaultState::try_deserialize(&mut &account.data[..]).unwrap()
+}
+
+/// Rent-exempt minimum of the data-less vault, what `initialize` seeds it with.
+pub fn vault_rent(svm: &LiteSVM) -> u64 {
+    svm.minimum_balance_for_rent_exemption(0)
+}
+
+pub fn balance(svm: &LiteSVM, key: &Pubkey) -> u64 {
+    svm.get_balance(key).unwrap_or(0)
+}
+
+/// `owner`'s associated token account for `mint`.
+pub fn ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+/// A new mint under `token_program`, with `amount` minted to the owner's ATA.
+pub fn create_mint(
+    svm: &mut LiteSVM,
+    owner: &Keypair,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let mint = Keypair::new();
+    let key = owner.pubkey();
+    let instructions = [
+        create_account(
+            &key,
+            &mint.pubkey(),
+            svm.minimum_balance_for_rent_exemption(Mint::LEN),
+            Mint::LEN as u64,
+            token_program,
+        ),
+        initialize_mint2(token_program, &mint.pubkey(), &key, None, DECIMALS).unwrap(),
+        create_associated_token_account(&key, &key, &mint.pubkey(), token_program),
+        mint_to(
+            token_program,
+            &mint.pubkey(),
+            &ata(&key, &mint.pubkey(), token_program),
+            &key,
+            &[],
+            amount,
+        )
+        .unwrap(),
+    ];
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&key),
+        &[owner, &mint],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+    svm.expire_blockhash();
+    mint.pubkey()
+}
+
+/// Balance of a token account, `None` once it is closed.
+pub fn token_balance(svm: &LiteSVM, key: &Pubkey) -> Option<u64> {
+    let account = svm.get_account(key)?;
+    TokenAccount::try_deserialize(&mut &account.data[..])
+        .ok()
+        .map(|account| account.amount)
+}
+
+pub fn initialize_ix(owner: &Pubkey, lock: Lock) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::Initialize {
+            user: *owner,
+            vault_state: state_pda(owner),
+            vault: vault_pda(owner),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::Initialize { lock }.data(),
+    }
+}
+
+pub fn deposit_ix(owner: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::Deposit {
+            user: *owner,
+            vault_state: state_pda(owner),
+            vault: vault_pda(owner),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::Deposit { amount }.data(),
+    }
+}
+
+pub fn withdraw_ix(owner: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::Withdraw {
+            user: *owner,
+            vault_state: state_pda(owner),
+            vault: vault_pda(owner),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::Withdraw { amount }.data(),
+    }
+}
+
+pub fn close_ix(owner: &Pubkey) -> Instruction {
+    close_with_tokens_ix(owner, &[])
+}
+
+/// `close` that also drains the vault's ATA of each `(mint, token_program)`.
+pub fn close_with_tokens_ix(owner: &Pubkey, tokens: &[(Pubkey, Pubkey)]) -> Instruction {
+    let mut accounts = anchor_vault_q4_25::accounts::Close {
+        user: *owner,
+        vault_state: state_pda(owner),
+        vault: vault_pda(owner),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    for (mint, token_program) in tokens {
+        accounts.extend([
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(ata(&vault_pda(owner), mint, token_program), false),
+            AccountMeta::new(ata(owner, mint, token_program), false),
+            AccountMeta::new_readonly(*token_program, false),
+        ]);
+    }
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts,
+        data: anchor_vault_q4_25::instruction::Close {}.data(),
+    }
+}
+
+pub fn tighten_lock_ix(owner: &Pubkey, lock: Lock) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::TightenLock {
+            user: *owner,
+            vault_state: state_pda(owner),
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::TightenLock { lock }.data(),
+    }
+}
+
+pub fn deposit_spl_ix(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::DepositSpl {
+            user: *owner,
+            vault_state: state_pda(owner),
+            vault: vault_pda(owner),
+            mint: *mint,
+            user_token_account: ata(owner, mint, token_program),
+            vault_token_account: ata(&vault_pda(owner), mint, token_program),
+            associated_token_program: associated_token::ID,
+            token_program: *token_program,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::DepositSpl { amount }.data(),
+    }
+}
+
+pub fn withdraw_spl_ix(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::WithdrawSpl {
+            user: *owner,
+            vault_state: state_pda(owner),
+            vault: vault_pda(owner),
+            mint: *mint,
+            user_token_account: ata(owner, mint, token_program),
+            vault_token_account: ata(&vault_pda(owner), mint, token_program),
+            associated_token_program: associated_token::ID,
+            token_program: *token_program,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::WithdrawSpl { amount }.data(),
+    }
+}
+
+pub fn close_spl_ix(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::CloseSpl {
+            user: *owner,
+            vault_state: state_pda(owner),
+            vault: vault_pda(owner),
+            mint: *mint,
+            user_token_account: ata(owner, mint, token_program),
+            vault_token_account: ata(&vault_pda(owner), mint, token_program),
+            associated_token_program: associated_token::ID,
+            token_program: *token_program,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::CloseSpl {}.data(),
+    }
+}
+
+pub fn approve_ix(owner: &Pubkey, delegate: &Pubkey, cap: u64, period: i64) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::Approve {
+            user: *owner,
+            vault_state: state_pda(owner),
+            vault: vault_pda(owner),
+            delegate: *delegate,
+            allowance: allowance_pda(owner, delegate),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::Approve { cap, period }.data(),
+    }
+}
+
+pub fn revoke_ix(owner: &Pubkey, delegate: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::Revoke {
+            user: *owner,
+            vault_state: state_pda(owner),
+            vault: vault_pda(owner),
+            delegate: *delegate,
+            allowance: allowance_pda(owner, delegate),
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::Revoke {}.data(),
+    }
+}
+
+pub fn delegate_withdraw_ix(owner: &Pubkey, delegate: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::DelegateWithdraw {
+            delegate: *delegate,
+            user: *owner,
+            vault_state: state_pda(owner),
+            vault: vault_pda(owner),
+            allowance: allowance_pda(owner, delegate),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::DelegateWithdraw { amount }.data(),
+    }
+}
+
+pub fn migrate_ix(owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: anchor_vault_q4_25::ID,
+        accounts: anchor_vault_q4_25::accounts::Migrate {
+            user: *owner,
+            vault_state: state_pda(owner),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_vault_q4_25::instruction::Migrate {}.data(),
+    }
+}
+
+/// A vault as the original program left it: a state holding only the two
+/// bumps, and `lamports` in the vault.
+pub fn create_v0_vault(svm: &mut LiteSVM, owner: &Pubkey, lamports: u64) {
+    let (state, state_bump) =
+        Pubkey::find_program_address(&[b"state", owner.as_ref()], &anchor_vault_q4_25::ID);
+    let (vault, vault_bump) =
+        Pubkey::find_program_address(&[b"vault", state.as_ref()], &anchor_vault_q4_25::ID);
+    let mut data = VaultState::DISCRIMINATOR.to_vec();
+    data.extend([vault_bump, state_bump]);
+    svm.set_account(
+        state,
+        Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: anchor_vault_q4_25::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+    svm.airdrop(&vault, lamports).unwrap();
+}
+
+#[allow(clippy::result_large_err)]
+pub fn send(
+    svm: &mut LiteSVM,
+    instructions: &[Instruction],
+    payer: &Keypair,
+) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    svm.expire_blockhash();
+    result
+}
+
+/// Failure of the instruction at `index` with Anchor's own `error`.
+pub fn anchor_error(index: u8, error: anchor_lang::error::ErrorCode) -> TransactionError {
+    TransactionError::InstructionError(index, InstructionError::Custom(error.into()))
+}
+
+/// Failure of the instruction at `index` with `error`.
+pub fn vault_error(index: u8, error: VaultError) -> TransactionError {
+    TransactionError::InstructionError(index, InstructionError::Custom(error.into()))
+}
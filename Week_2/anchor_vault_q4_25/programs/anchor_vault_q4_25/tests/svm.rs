@@ -0,0 +1,504 @@
+// In-process tests of the SBF build of the vault, no validator needed:
+//
+//   anchor build
+//   cargo test -p anchor_vault_q4_25 --test svm -- --ignored
+
+mod common;
+
+use anchor_spl::{token, token_2022};
+use anchor_vault_q4_25::{Lock, VaultError, MAX_MINTS, VAULT_STATE_VERSION};
+use common::*;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+const DAY: i64 = 24 * 60 * 60;
+
+fn time_lock(unlock_at: i64) -> Lock {
+    Lock {
+        unlock_at: Some(unlock_at),
+        target_balance: None,
+    }
+}
+
+fn goal(target_balance: u64) -> Lock {
+    Lock {
+        unlock_at: None,
+        target_balance: Some(target_balance),
+    }
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn unlocked_vault_withdraws_at_any_time() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    send(
+        &mut svm,
+        &[initialize_ix(&key, Lock::default()), deposit_ix(&key, SOL)],
+        &owner,
+    )
+    .unwrap();
+
+    let before = balance(&svm, &vault_pda(&key));
+    send(&mut svm, &[withdraw_ix(&key, SOL / 2)], &owner).unwrap();
+    assert_eq!(balance(&svm, &vault_pda(&key)), before - SOL / 2);
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn time_lock_holds_until_unlock_at() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    send(
+        &mut svm,
+        &[initialize_ix(&key, time_lock(NOW + DAY)), deposit_ix(&key, SOL)],
+        &owner,
+    )
+    .unwrap();
+
+    let err = send(&mut svm, &[withdraw_ix(&key, SOL / 2)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TimeLocked));
+    let err = send(&mut svm, &[close_ix(&key)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TimeLocked));
+
+    set_time(&mut svm, NOW + DAY);
+    send(&mut svm, &[withdraw_ix(&key, SOL / 2)], &owner).unwrap();
+    send(&mut svm, &[close_ix(&key)], &owner).unwrap();
+    assert_eq!(balance(&svm, &vault_pda(&key)), 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn goal_lock_opens_once_the_target_is_reached() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    send(&mut svm, &[initialize_ix(&key, goal(3 * SOL))], &owner).unwrap();
+    send(&mut svm, &[deposit_ix(&key, 2 * SOL)], &owner).unwrap();
+
+    let err = send(&mut svm, &[withdraw_ix(&key, SOL)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TargetNotReached));
+
+    send(&mut svm, &[deposit_ix(&key, SOL)], &owner).unwrap();
+    send(&mut svm, &[withdraw_ix(&key, 2 * SOL)], &owner).unwrap();
+    assert_eq!(vault_state(&svm, &key).lock.target_balance, None);
+
+    // below the target again, but the goal was met
+    send(&mut svm, &[withdraw_ix(&key, SOL / 2)], &owner).unwrap();
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn combined_lock_needs_both_conditions() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    let lock = Lock {
+        unlock_at: Some(NOW + DAY),
+        target_balance: Some(2 * SOL),
+    };
+    send(
+        &mut svm,
+        &[initialize_ix(&key, lock), deposit_ix(&key, 2 * SOL)],
+        &owner,
+    )
+    .unwrap();
+
+    let err = send(&mut svm, &[withdraw_ix(&key, SOL)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TimeLocked));
+
+    set_time(&mut svm, NOW + DAY);
+    send(&mut svm, &[withdraw_ix(&key, SOL)], &owner).unwrap();
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn tighten_lock_is_one_way() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    send(&mut svm, &[initialize_ix(&key, time_lock(NOW + DAY))], &owner).unwrap();
+
+    let later = Lock {
+        unlock_at: Some(NOW + 2 * DAY),
+        target_balance: Some(SOL),
+    };
+    send(&mut svm, &[tighten_lock_ix(&key, later)], &owner).unwrap();
+    assert_eq!(vault_state(&svm, &key).lock, later);
+
+    for looser in [
+        time_lock(NOW + 2 * DAY),
+        goal(SOL),
+        Lock {
+            unlock_at: Some(NOW + DAY),
+            target_balance: Some(SOL),
+        },
+        Lock {
+            unlock_at: Some(NOW + 2 * DAY),
+            target_balance: Some(SOL - 1),
+        },
+    ] {
+        let err = send(&mut svm, &[tighten_lock_ix(&key, looser)], &owner).unwrap_err();
+        assert_eq!(err.err, vault_error(0, VaultError::LockLoosened));
+    }
+    assert_eq!(vault_state(&svm, &key).lock, later);
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn deposits_and_withdrawals_are_recorded() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    send(&mut svm, &[initialize_ix(&key, Lock::default())], &owner).unwrap();
+    assert_eq!(balance(&svm, &vault_pda(&key)), vault_rent(&svm));
+
+    svm.warp_to_slot(10);
+    send(&mut svm, &[deposit_ix(&key, 2 * SOL)], &owner).unwrap();
+    send(&mut svm, &[deposit_ix(&key, SOL)], &owner).unwrap();
+    svm.warp_to_slot(20);
+    send(&mut svm, &[withdraw_ix(&key, SOL / 2)], &owner).unwrap();
+
+    let state = vault_state(&svm, &key);
+    assert_eq!(state.total_deposited, 3 * SOL);
+    assert_eq!(state.total_withdrawn, SOL / 2);
+    assert_eq!(state.last_activity_slot, 20);
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn withdraw_keeps_the_vault_rent_exempt() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    send(
+        &mut svm,
+        &[initialize_ix(&key, Lock::default()), deposit_ix(&key, SOL)],
+        &owner,
+    )
+    .unwrap();
+
+    let err = send(&mut svm, &[withdraw_ix(&key, SOL + 1)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::BelowRentExemption));
+    let err = send(&mut svm, &[withdraw_ix(&key, u64::MAX)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::InsufficientFunds));
+
+    // everything above the rent seed can go, the rest only through close
+    send(&mut svm, &[withdraw_ix(&key, SOL)], &owner).unwrap();
+    assert_eq!(balance(&svm, &vault_pda(&key)), vault_rent(&svm));
+    send(&mut svm, &[close_ix(&key)], &owner).unwrap();
+    assert_eq!(balance(&svm, &vault_pda(&key)), 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn spl_deposits_and_withdrawals_work_with_both_token_programs() {
+    for token_program in [token::ID, token_2022::ID] {
+        let (mut svm, owner) = setup();
+        let key = owner.pubkey();
+        let mint = create_mint(&mut svm, &owner, &token_program, 1_000);
+        let vault_ata = ata(&vault_pda(&key), &mint, &token_program);
+        send(&mut svm, &[initialize_ix(&key, Lock::default())], &owner).unwrap();
+
+        // the first deposit creates the vault's ATA, later ones reuse it
+        send(&mut svm, &[deposit_spl_ix(&key, &mint, &token_program, 600)], &owner).unwrap();
+        send(&mut svm, &[deposit_spl_ix(&key, &mint, &token_program, 100)], &owner).unwrap();
+        assert_eq!(token_balance(&svm, &vault_ata), Some(700));
+
+        send(&mut svm, &[withdraw_spl_ix(&key, &mint, &token_program, 200)], &owner).unwrap();
+        assert_eq!(token_balance(&svm, &vault_ata), Some(500));
+        assert_eq!(token_balance(&svm, &ata(&key, &mint, &token_program)), Some(500));
+    }
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn spl_withdrawals_wait_for_every_lock() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    let mint = create_mint(&mut svm, &owner, &token::ID, 1_000);
+    let lock = Lock {
+        unlock_at: Some(NOW + DAY),
+        target_balance: Some(10 * SOL),
+    };
+    send(
+        &mut svm,
+        &[
+            initialize_ix(&key, lock),
+            deposit_spl_ix(&key, &mint, &token::ID, 1_000),
+        ],
+        &owner,
+    )
+    .unwrap();
+
+    let err = send(&mut svm, &[withdraw_spl_ix(&key, &mint, &token::ID, 1)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TimeLocked));
+    let err = send(&mut svm, &[close_spl_ix(&key, &mint, &token::ID)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TimeLocked));
+
+    // past the unlock time the lamport target still holds the tokens
+    set_time(&mut svm, NOW + DAY);
+    let err = send(&mut svm, &[withdraw_spl_ix(&key, &mint, &token::ID, 1)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TargetNotReached));
+    let err = send(&mut svm, &[close_spl_ix(&key, &mint, &token::ID)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TargetNotReached));
+
+    send(&mut svm, &[deposit_ix(&key, 10 * SOL)], &owner).unwrap();
+    send(&mut svm, &[withdraw_spl_ix(&key, &mint, &token::ID, 1)], &owner).unwrap();
+    send(&mut svm, &[close_spl_ix(&key, &mint, &token::ID)], &owner).unwrap();
+    assert_eq!(token_balance(&svm, &ata(&vault_pda(&key), &mint, &token::ID)), None);
+    assert_eq!(token_balance(&svm, &ata(&key, &mint, &token::ID)), Some(1_000));
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn close_drains_and_closes_every_token_account() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    let classic = create_mint(&mut svm, &owner, &token::ID, 1_000);
+    let extended = create_mint(&mut svm, &owner, &token_2022::ID, 2_000);
+    let tokens = [(classic, token::ID), (extended, token_2022::ID)];
+    send(
+        &mut svm,
+        &[
+            initialize_ix(&key, Lock::default()),
+            deposit_spl_ix(&key, &classic, &token::ID, 1_000),
+            deposit_spl_ix(&key, &extended, &token_2022::ID, 2_000),
+        ],
+        &owner,
+    )
+    .unwrap();
+
+    // the ATA of another owner is not the vault's
+    let mut ix = close_with_tokens_ix(&key, &tokens[..1]);
+    ix.accounts[5].pubkey = ata(&key, &classic, &token::ID);
+    let err = send(&mut svm, &[ix], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::InvalidTokenAccounts));
+    let mut ix = close_with_tokens_ix(&key, &tokens[..1]);
+    ix.accounts.pop();
+    let err = send(&mut svm, &[ix], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::InvalidTokenAccounts));
+
+    send(&mut svm, &[close_with_tokens_ix(&key, &tokens)], &owner).unwrap();
+    for (mint, token_program) in tokens {
+        assert_eq!(token_balance(&svm, &ata(&vault_pda(&key), &mint, &token_program)), None);
+    }
+    assert_eq!(token_balance(&svm, &ata(&key, &classic, &token::ID)), Some(1_000));
+    assert_eq!(token_balance(&svm, &ata(&key, &extended, &token_2022::ID)), Some(2_000));
+    assert_eq!(balance(&svm, &vault_pda(&key)), 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn close_needs_every_vault_token_account() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    let classic = create_mint(&mut svm, &owner, &token::ID, 1_000);
+    let extended = create_mint(&mut svm, &owner, &token_2022::ID, 2_000);
+    send(
+        &mut svm,
+        &[
+            initialize_ix(&key, Lock::default()),
+            deposit_spl_ix(&key, &classic, &token::ID, 1_000),
+            deposit_spl_ix(&key, &extended, &token_2022::ID, 2_000),
+        ],
+        &owner,
+    )
+    .unwrap();
+    assert_eq!(vault_state(&svm, &key).mints, [classic, extended]);
+
+    let err = send(&mut svm, &[close_ix(&key)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TokenAccountsOpen));
+    let err = send(
+        &mut svm,
+        &[close_with_tokens_ix(&key, &[(classic, token::ID)])],
+        &owner,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TokenAccountsOpen));
+
+    // an emptied account is still open, only `close_spl` lets the vault forget it
+    send(&mut svm, &[withdraw_spl_ix(&key, &extended, &token_2022::ID, 2_000)], &owner).unwrap();
+    let err = send(
+        &mut svm,
+        &[close_with_tokens_ix(&key, &[(classic, token::ID)])],
+        &owner,
+    )
+    .unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TokenAccountsOpen));
+    send(&mut svm, &[close_spl_ix(&key, &extended, &token_2022::ID)], &owner).unwrap();
+    assert_eq!(vault_state(&svm, &key).mints, [classic]);
+
+    send(
+        &mut svm,
+        &[close_with_tokens_ix(&key, &[(classic, token::ID)])],
+        &owner,
+    )
+    .unwrap();
+    assert_eq!(token_balance(&svm, &ata(&key, &classic, &token::ID)), Some(1_000));
+    assert_eq!(balance(&svm, &vault_pda(&key)), 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn deposits_stop_at_max_mints() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    send(&mut svm, &[initialize_ix(&key, Lock::default())], &owner).unwrap();
+
+    for _ in 0..MAX_MINTS {
+        let mint = create_mint(&mut svm, &owner, &token::ID, 1);
+        send(&mut svm, &[deposit_spl_ix(&key, &mint, &token::ID, 1)], &owner).unwrap();
+    }
+    let mint = create_mint(&mut svm, &owner, &token::ID, 1);
+    let err = send(&mut svm, &[deposit_spl_ix(&key, &mint, &token::ID, 1)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TooManyMints));
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn delegate_withdraws_up_to_the_cap_each_period() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    let delegate = Keypair::new();
+    svm.airdrop(&delegate.pubkey(), SOL).unwrap();
+    send(
+        &mut svm,
+        &[
+            initialize_ix(&key, Lock::default()),
+            deposit_ix(&key, 10 * SOL),
+            approve_ix(&key, &delegate.pubkey(), 2 * SOL, DAY),
+        ],
+        &owner,
+    )
+    .unwrap();
+
+    let before = balance(&svm, &delegate.pubkey());
+    send(&mut svm, &[delegate_withdraw_ix(&key, &delegate.pubkey(), SOL)], &delegate).unwrap();
+    send(&mut svm, &[delegate_withdraw_ix(&key, &delegate.pubkey(), SOL)], &delegate).unwrap();
+    let err = send(&mut svm, &[delegate_withdraw_ix(&key, &delegate.pubkey(), 1)], &delegate)
+        .unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::AllowanceExceeded));
+
+    // two idle days later a single fresh period opens
+    set_time(&mut svm, NOW + 2 * DAY + 1);
+    send(&mut svm, &[delegate_withdraw_ix(&key, &delegate.pubkey(), 2 * SOL)], &delegate).unwrap();
+    let state = allowance(&svm, &key, &delegate.pubkey());
+    assert_eq!(state.period_start, NOW + 2 * DAY);
+    assert_eq!(state.spent, 2 * SOL);
+
+    // four withdrawals, four transaction fees paid by the delegate
+    assert_eq!(balance(&svm, &delegate.pubkey()), before + 4 * SOL - 4 * 5_000);
+    assert_eq!(vault_state(&svm, &key).total_withdrawn, 4 * SOL);
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn revoked_or_locked_allowances_pay_nothing() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    let delegate = Keypair::new();
+    svm.airdrop(&delegate.pubkey(), SOL).unwrap();
+    send(
+        &mut svm,
+        &[
+            initialize_ix(&key, time_lock(NOW + DAY)),
+            deposit_ix(&key, 10 * SOL),
+            approve_ix(&key, &delegate.pubkey(), SOL, DAY),
+        ],
+        &owner,
+    )
+    .unwrap();
+
+    let err = send(&mut svm, &[delegate_withdraw_ix(&key, &delegate.pubkey(), SOL)], &delegate)
+        .unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::TimeLocked));
+
+    set_time(&mut svm, NOW + DAY);
+    send(&mut svm, &[revoke_ix(&key, &delegate.pubkey())], &owner).unwrap();
+    assert_eq!(balance(&svm, &allowance_pda(&key, &delegate.pubkey())), 0);
+    let err = send(&mut svm, &[delegate_withdraw_ix(&key, &delegate.pubkey(), SOL)], &delegate)
+        .unwrap_err();
+    assert_eq!(
+        err.err,
+        anchor_error(0, anchor_lang::error::ErrorCode::AccountNotInitialized)
+    );
+
+    let err = send(&mut svm, &[approve_ix(&key, &delegate.pubkey(), SOL, 0)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::InvalidPeriod));
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn close_needs_every_allowance_revoked() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    let (alice, bob) = (Keypair::new(), Keypair::new());
+    svm.airdrop(&alice.pubkey(), SOL).unwrap();
+    send(
+        &mut svm,
+        &[
+            initialize_ix(&key, Lock::default()),
+            deposit_ix(&key, 5 * SOL),
+            approve_ix(&key, &alice.pubkey(), SOL, DAY),
+            approve_ix(&key, &bob.pubkey(), SOL, DAY),
+        ],
+        &owner,
+    )
+    .unwrap();
+    // changing an allowance does not open another one
+    send(&mut svm, &[approve_ix(&key, &bob.pubkey(), 2 * SOL, DAY)], &owner).unwrap();
+    assert_eq!(vault_state(&svm, &key).allowances, 2);
+
+    let err = send(&mut svm, &[close_ix(&key)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::AllowancesOpen));
+    send(&mut svm, &[revoke_ix(&key, &bob.pubkey())], &owner).unwrap();
+    let err = send(&mut svm, &[close_ix(&key)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::AllowancesOpen));
+    send(
+        &mut svm,
+        &[revoke_ix(&key, &alice.pubkey()), close_ix(&key)],
+        &owner,
+    )
+    .unwrap();
+
+    // a vault opened again at the same address starts without delegates
+    send(
+        &mut svm,
+        &[
+            initialize_ix(&key, Lock::default()),
+            deposit_ix(&key, 5 * SOL),
+        ],
+        &owner,
+    )
+    .unwrap();
+    assert_eq!(vault_state(&svm, &key).allowances, 0);
+    let err = send(&mut svm, &[delegate_withdraw_ix(&key, &alice.pubkey(), SOL)], &alice)
+        .unwrap_err();
+    assert_eq!(
+        err.err,
+        anchor_error(0, anchor_lang::error::ErrorCode::AccountNotInitialized)
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy/anchor_vault_q4_25.so from `anchor build`"]
+fn migrate_upgrades_an_original_vault() {
+    let (mut svm, owner) = setup();
+    let key = owner.pubkey();
+    create_v0_vault(&mut svm, &key, 2 * SOL);
+
+    // the current program cannot read the original layout until it is migrated
+    let err = send(&mut svm, &[withdraw_ix(&key, SOL)], &owner).unwrap_err();
+    assert_eq!(
+        err.err,
+        anchor_error(0, anchor_lang::error::ErrorCode::AccountDidNotDeserialize)
+    );
+
+    send(&mut svm, &[migrate_ix(&key)], &owner).unwrap();
+    let state = vault_state(&svm, &key);
+    assert_eq!(state.version, VAULT_STATE_VERSION);
+    assert_eq!(state.lock, Lock::default());
+    assert_eq!((state.total_deposited, state.total_withdrawn), (0, 0));
+
+    let err = send(&mut svm, &[migrate_ix(&key)], &owner).unwrap_err();
+    assert_eq!(err.err, vault_error(0, VaultError::AlreadyMigrated));
+
+    send(&mut svm, &[withdraw_ix(&key, SOL)], &owner).unwrap();
+    assert_eq!(vault_state(&svm, &key).total_withdrawn, SOL);
+}
@@ -30,7 +30,7 @@ describe("anchor_vault", () => {
 
   it("Initialize the vault", async () => {
     await program.methods
-      .initialize()
+      .initialize({ unlockAt: null, targetBalance: null })
       .accountsStrict({
         user: user,
         vaultState: vaultStatePda,